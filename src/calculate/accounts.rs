@@ -0,0 +1,272 @@
+use super::{events::LifeEvents, saver::Saver, tax::Taxpayer};
+
+// annual 401k and ira contribution limits in today's dollars
+pub const PRE_TAX_CONTRIBUTION_LIMIT: f32 = 23000.0;
+pub const ROTH_CONTRIBUTION_LIMIT: f32 = 7000.0;
+// uniform lifetime table divisors for ages 72 through 99
+pub const RMD_FIRST_AGE: u8 = 72;
+pub const RMD_DIVISORS: [f32; 28] = [
+    27.4, 26.5, 25.5, 24.6, 23.7, 22.9, 22.0, 21.1, 20.2, 19.4, 18.5, 17.7, 16.8, 16.0, 15.2, 14.4,
+    13.7, 12.9, 12.2, 11.5, 10.8, 10.1, 9.5, 8.9, 8.4, 7.8, 7.3, 6.8,
+];
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Account {
+    // the liquid savings not held in a retirement account
+    Taxable,
+    PreTax,
+    Roth,
+}
+
+// every order the accounts can be drawn down in
+pub const WITHDRAWAL_ORDERS: [[Account; 3]; 6] = [
+    [Account::Taxable, Account::PreTax, Account::Roth],
+    [Account::Taxable, Account::Roth, Account::PreTax],
+    [Account::PreTax, Account::Taxable, Account::Roth],
+    [Account::PreTax, Account::Roth, Account::Taxable],
+    [Account::Roth, Account::Taxable, Account::PreTax],
+    [Account::Roth, Account::PreTax, Account::Taxable],
+];
+
+// each earner puts the same share of their own wages in, up to their own annual limit
+fn member_contributions(member_wages: [f32; 2], rate: f32, annual_limit: f32) -> [f32; 2] {
+    member_wages.map(|wages| (wages * rate).min(annual_limit / 12.0))
+}
+
+pub fn rmd_divisor(age: u8) -> f32 {
+    RMD_DIVISORS[(age.saturating_sub(RMD_FIRST_AGE) as usize).min(RMD_DIVISORS.len() - 1)]
+}
+
+pub trait Accounts<Saver> {
+    fn taxable_savings(&self) -> f32;
+    fn monthly_pre_tax_contribution(&self) -> f32;
+    fn monthly_employer_match(&self) -> f32;
+    fn monthly_roth_contribution(&self) -> f32;
+    fn open_accounts(&mut self);
+    fn contribute(&mut self, pre_tax: f32, roth: f32);
+    fn grow_accounts(&mut self);
+    fn withdraw_pre_tax(&mut self, amount: f32, periods: f32) -> f32;
+    fn settle_taxable_change(&mut self, taxable_before: f32, periods: f32);
+    fn take_required_distribution(&mut self);
+}
+
+impl Accounts<Saver<'_>> for Saver<'_> {
+    // whatever liquid savings aren't in the pre-tax or roth accounts
+    fn taxable_savings(&self) -> f32 {
+        self.liquid_assets() - self.pre_tax_savings - self.roth_savings
+    }
+
+    // paid out of wages before income tax, up to each earner's annual limit
+    fn monthly_pre_tax_contribution(&self) -> f32 {
+        if self.active_retirement {
+            return 0.0;
+        }
+        member_contributions(
+            self.monthly_member_wages(),
+            self.pre_tax_contribution_rate,
+            PRE_TAX_CONTRIBUTION_LIMIT * self.price_level,
+        )
+        .iter()
+        .sum()
+    }
+
+    // each employer matches pre-tax contributions up to a percent of that earner's income
+    fn monthly_employer_match(&self) -> f32 {
+        if self.active_retirement {
+            return 0.0;
+        }
+        let member_wages = self.monthly_member_wages();
+        member_contributions(
+            member_wages,
+            self.pre_tax_contribution_rate,
+            PRE_TAX_CONTRIBUTION_LIMIT * self.price_level,
+        )
+        .iter()
+        .zip(member_wages)
+        .map(|(contribution, wages)| contribution.min(wages * self.employer_match_rate))
+        .sum()
+    }
+
+    // paid out of take home pay, up to each earner's annual limit
+    fn monthly_roth_contribution(&self) -> f32 {
+        if self.active_retirement {
+            return 0.0;
+        }
+        member_contributions(
+            self.monthly_member_wages(),
+            self.roth_contribution_rate,
+            ROTH_CONTRIBUTION_LIMIT * self.price_level,
+        )
+        .iter()
+        .sum()
+    }
+
+    // starting balances can't be more than the liquid savings, the taxable account is fully paid in
+    fn open_accounts(&mut self) {
+        self.pre_tax_savings = self.pre_tax_savings.min(self.liquid_assets());
+        self.roth_savings = self
+            .roth_savings
+            .min(self.liquid_assets() - self.pre_tax_savings);
+        self.cost_basis = self.taxable_savings();
+    }
+
+    fn contribute(&mut self, pre_tax: f32, roth: f32) {
+        self.pre_tax_savings += pre_tax;
+        self.roth_savings += roth;
+    }
+
+    // retirement accounts earn the same return as the taxable account
+    fn grow_accounts(&mut self) {
+        let monthly_interest = self.monthly_interest();
+        self.pre_tax_savings *= 1.0 + monthly_interest;
+        self.roth_savings *= 1.0 + monthly_interest;
+    }
+
+    // take enough out of the pre-tax account to net the amount after income tax, returns what it netted
+    fn withdraw_pre_tax(&mut self, amount: f32, periods: f32) -> f32 {
+        let mut gross = amount;
+        for _ in 0..4 {
+            gross = amount + self.ordinary_income_tax(gross * periods) / periods;
+        }
+        let gross = gross.min(self.pre_tax_savings).max(0.0);
+        let tax = self.ordinary_income_tax(gross * periods) / periods;
+        self.pre_tax_savings -= gross;
+        self.total_savings -= tax;
+        gross - tax
+    }
+
+    // money added to the taxable account raises its cost basis, money taken out is drawn from the accounts
+    // in the withdrawal order (periods is how many such changes make a year)
+    fn settle_taxable_change(&mut self, taxable_before: f32, periods: f32) {
+        let change = self.taxable_savings() - taxable_before;
+        if change >= 0.0 {
+            self.cost_basis += change;
+            return;
+        }
+        let mut needed = -change;
+        for account in self.withdrawal_order {
+            if needed <= 0.0 {
+                break;
+            }
+            match account {
+                // sells a proportional slice of basis and gains, the gain pays capital gains tax
+                Account::Taxable if taxable_before > 0.0 => {
+                    let sold = needed.min(taxable_before);
+                    let basis = self.cost_basis.min(taxable_before);
+                    let gain = sold * (1.0 - basis / taxable_before);
+                    self.cost_basis -= basis * sold / taxable_before;
+                    self.total_savings -= self.capital_gains_tax(gain * periods) / periods;
+                    needed -= sold;
+                }
+                Account::Taxable => {}
+                Account::PreTax => {
+                    needed -= self.withdraw_pre_tax(needed, periods);
+                }
+                Account::Roth => {
+                    let withdrawn = needed.min(self.roth_savings);
+                    self.roth_savings -= withdrawn;
+                    needed -= withdrawn;
+                }
+            }
+        }
+    }
+
+    // once past the rmd age a slice of the pre-tax account is taxed and moved to the taxable account every year
+    fn take_required_distribution(&mut self) {
+        if self.current_age < self.rmd_age || self.pre_tax_savings <= 0.0 {
+            return;
+        }
+        let distribution = self.pre_tax_savings / rmd_divisor(self.current_age);
+        let tax = self.ordinary_income_tax(distribution);
+        self.pre_tax_savings -= distribution;
+        self.total_savings -= tax;
+        self.cost_basis += distribution - tax;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calculate::saver::tests::saver;
+
+    #[test]
+    fn rmd_divisor_follows_the_uniform_lifetime_table() {
+        assert_eq!(rmd_divisor(72), 27.4);
+        assert_eq!(rmd_divisor(75), 24.6);
+        assert_eq!(rmd_divisor(99), 6.8);
+        // held at the ends of the table
+        assert_eq!(rmd_divisor(60), 27.4);
+        assert_eq!(rmd_divisor(110), 6.8);
+    }
+
+    #[test]
+    fn contributions_stop_at_the_annual_limits() {
+        let mut worker = Saver {
+            pre_tax_contribution_rate: 0.1,
+            roth_contribution_rate: 0.1,
+            employer_match_rate: 0.04,
+            ..saver()
+        };
+        assert!((worker.monthly_pre_tax_contribution() - 1400.0).abs() < 0.01);
+        assert!((worker.monthly_roth_contribution() - 7000.0 / 12.0).abs() < 0.01);
+        assert!((worker.monthly_employer_match() - 560.0).abs() < 0.01);
+        worker.pre_tax_contribution_rate = 0.2;
+        assert!((worker.monthly_pre_tax_contribution() - 23000.0 / 12.0).abs() < 0.01);
+        worker.active_retirement = true;
+        assert_eq!(worker.monthly_pre_tax_contribution(), 0.0);
+        assert_eq!(worker.monthly_employer_match(), 0.0);
+    }
+
+    #[test]
+    fn taxable_withdrawals_sell_a_proportional_slice_of_basis() {
+        let mut investor = Saver {
+            cost_basis: 50000.0,
+            ..saver()
+        };
+        // 100k in the taxable account, half of it gains
+        let taxable = investor.taxable_savings();
+        investor.total_savings -= 20000.0;
+        investor.settle_taxable_change(taxable, 1.0);
+        assert_eq!(investor.cost_basis, 40000.0);
+        let taxable = investor.taxable_savings();
+        investor.total_savings += 5000.0;
+        investor.settle_taxable_change(taxable, 1.0);
+        assert_eq!(investor.cost_basis, 45000.0);
+    }
+
+    #[test]
+    fn spending_is_drawn_from_the_accounts_in_the_withdrawal_order() {
+        let mut retiree = Saver {
+            roth_savings: 30000.0,
+            withdrawal_order: [Account::Roth, Account::Taxable, Account::PreTax],
+            ..saver()
+        };
+        retiree.open_accounts();
+        let taxable = retiree.taxable_savings();
+        retiree.total_savings -= 20000.0;
+        retiree.settle_taxable_change(taxable, 1.0);
+        // the roth account covers it, the taxable account is made whole and keeps its basis
+        assert_eq!(retiree.roth_savings, 10000.0);
+        assert_eq!(retiree.taxable_savings(), taxable);
+        assert_eq!(retiree.cost_basis, taxable);
+    }
+
+    #[test]
+    fn required_distributions_move_pre_tax_savings_to_taxable() {
+        let mut retiree = Saver {
+            current_age: 72,
+            rmd_age: 72,
+            pre_tax_savings: 27400.0,
+            ..saver()
+        };
+        retiree.take_required_distribution();
+        assert!((retiree.pre_tax_savings - 26400.0).abs() < 0.01);
+        assert!((retiree.cost_basis - 1000.0).abs() < 0.01);
+        let mut young = Saver {
+            current_age: 71,
+            ..retiree.clone()
+        };
+        young.take_required_distribution();
+        assert_eq!(young.pre_tax_savings, retiree.pre_tax_savings);
+    }
+}
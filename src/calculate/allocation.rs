@@ -0,0 +1,271 @@
+use super::rates::RatePath;
+
+// shares of savings in stocks, bonds and cash, they add up to one
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Mix {
+    pub stocks: f32,
+    pub bonds: f32,
+    pub cash: f32,
+}
+
+impl Mix {
+    // negative shares are dropped and the rest scaled to add up to one, all cash when nothing is left
+    pub fn normalized(stocks: f32, bonds: f32, cash: f32) -> Self {
+        let (stocks, bonds, cash) = (stocks.max(0.0), bonds.max(0.0), cash.max(0.0));
+        let total = stocks + bonds + cash;
+        if total > 0.0 {
+            Mix {
+                stocks: stocks / total,
+                bonds: bonds / total,
+                cash: cash / total,
+            }
+        } else {
+            Mix {
+                stocks: 0.0,
+                bonds: 0.0,
+                cash: 1.0,
+            }
+        }
+    }
+
+    pub fn portfolio_return(&self, stocks: f32, bonds: f32, cash: f32) -> f32 {
+        self.stocks * stocks + self.bonds * bonds + self.cash * cash
+    }
+
+    // the mix after a year of returns without rebalancing, winners grow their share
+    pub fn grown(&self, stocks: f32, bonds: f32, cash: f32) -> Self {
+        Mix::normalized(
+            self.stocks * (1.0 + stocks),
+            self.bonds * (1.0 + bonds),
+            self.cash * (1.0 + cash),
+        )
+    }
+
+    // moved by the change between two targets, new contributions and sales follow the glide path
+    pub fn shifted(&self, from: &Mix, to: &Mix) -> Self {
+        Mix::normalized(
+            self.stocks + to.stocks - from.stocks,
+            self.bonds + to.bonds - from.bonds,
+            self.cash + to.cash - from.cash,
+        )
+    }
+
+    fn lerp(&self, other: &Mix, t: f32) -> Self {
+        Mix::normalized(
+            self.stocks + (other.stocks - self.stocks) * t,
+            self.bonds + (other.bonds - self.bonds) * t,
+            self.cash + (other.cash - self.cash) * t,
+        )
+    }
+}
+
+// target allocation by age
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum GlidePath {
+    // stocks and bonds only, from 80% stocks when young down to 40% late in life
+    #[default]
+    AgeBands,
+    // a target date fund, mostly stocks until the years before retirement then bonds and some cash
+    TargetDate,
+    // straight lines between the mix at each breakpoint age, flat before the first and after the last
+    Custom(Vec<(u8, Mix)>),
+}
+
+// the age band allocation's share of savings in stocks, the rest is in bonds, risk falls with age
+pub fn stock_share(age: u8) -> f32 {
+    match age {
+        0..=35 => 0.8,
+        36..=49 => 0.7,
+        50..=64 => 0.6,
+        65..=80 => 0.5,
+        _ => 0.4,
+    }
+}
+
+const TARGET_DATE_STOCKS: f32 = 0.9;
+const RETIREMENT_STOCKS: f32 = 0.5;
+const LATE_RETIREMENT_STOCKS: f32 = 0.3;
+const RETIREMENT_CASH: f32 = 0.05;
+// the glide starts this many years before retirement and settles this many years after
+const GLIDE_START_YEARS: f32 = 25.0;
+const GLIDE_END_YEARS: f32 = 7.0;
+
+impl GlidePath {
+    pub fn mix(&self, age: u8, retirement_age: u8) -> Mix {
+        match self {
+            GlidePath::AgeBands => {
+                let stocks = stock_share(age);
+                Mix::normalized(stocks, 1.0 - stocks, 0.0)
+            }
+            GlidePath::TargetDate => {
+                let years_left = retirement_age as f32 - age as f32;
+                let (stocks, cash) = if years_left >= GLIDE_START_YEARS {
+                    (TARGET_DATE_STOCKS, 0.0)
+                } else if years_left >= 0.0 {
+                    let t = years_left / GLIDE_START_YEARS;
+                    (
+                        RETIREMENT_STOCKS + (TARGET_DATE_STOCKS - RETIREMENT_STOCKS) * t,
+                        RETIREMENT_CASH * (1.0 - t),
+                    )
+                } else {
+                    let t = (-years_left / GLIDE_END_YEARS).min(1.0);
+                    (
+                        RETIREMENT_STOCKS - (RETIREMENT_STOCKS - LATE_RETIREMENT_STOCKS) * t,
+                        RETIREMENT_CASH,
+                    )
+                };
+                Mix::normalized(stocks, 1.0 - stocks - cash, cash)
+            }
+            GlidePath::Custom(breakpoints) => {
+                let mut breakpoints = breakpoints.clone();
+                breakpoints.sort_by_key(|(age, _)| *age);
+                let after = breakpoints.iter().position(|(start, _)| *start > age);
+                match after {
+                    None => breakpoints.last().map(|(_, mix)| *mix),
+                    Some(0) => breakpoints.first().map(|(_, mix)| *mix),
+                    Some(idx) => {
+                        let (from_age, from) = breakpoints[idx - 1];
+                        let (to_age, to) = breakpoints[idx];
+                        let t = (age - from_age) as f32 / (to_age - from_age) as f32;
+                        Some(from.lerp(&to, t))
+                    }
+                }
+                .unwrap_or(Mix::normalized(1.0, 0.0, 0.0))
+            }
+        }
+    }
+
+    // the ages the allocation changes course, where an age band, the glide or a breakpoint starts
+    fn turning_ages(&self, retirement_age: u8) -> Vec<u8> {
+        match self {
+            GlidePath::AgeBands => (1..=u8::MAX)
+                .filter(|age| stock_share(*age) != stock_share(age - 1))
+                .collect(),
+            GlidePath::TargetDate => vec![
+                retirement_age.saturating_sub(GLIDE_START_YEARS as u8),
+                retirement_age,
+                retirement_age.saturating_add(GLIDE_END_YEARS as u8),
+            ],
+            GlidePath::Custom(breakpoints) => breakpoints.iter().map(|(age, _)| *age).collect(),
+        }
+    }
+
+    // first and last age of each stretch of the run between the turning ages
+    pub fn spans(&self, start_age: u8, retirement_age: u8, last_age: u8) -> Vec<(u8, u8)> {
+        let mut turns = self.turning_ages(retirement_age);
+        turns.retain(|age| *age > start_age && *age <= last_age);
+        turns.sort();
+        turns.dedup();
+        let mut spans = Vec::with_capacity(turns.len() + 1);
+        let mut first = start_age;
+        for turn in turns {
+            spans.push((first, turn - 1));
+            first = turn;
+        }
+        spans.push((first, last_age));
+        spans
+    }
+
+    // the return on savings for each age of the rate path, the mix follows the glide path from the start age,
+    // reset to the target every year when rebalancing or drifting with each year's returns otherwise
+    pub fn portfolio_returns(
+        &self,
+        rates: &RatePath,
+        start_age: u8,
+        retirement_age: u8,
+        rebalancing: bool,
+    ) -> Vec<f32> {
+        let mut mix = self.mix(start_age, retirement_age);
+        (0..rates.stocks.len())
+            .map(|age| {
+                let target = self.mix(age as u8, retirement_age);
+                if rebalancing || age <= start_age as usize {
+                    mix = target;
+                }
+                let (stocks, bonds, cash) = (rates.stocks[age], rates.bonds[age], rates.cash[age]);
+                let portfolio_return = mix.portfolio_return(stocks, bonds, cash);
+                // new savings and sales follow the glide path, the rest of the mix moves with the returns
+                let next_target = self.mix(age.saturating_add(1) as u8, retirement_age);
+                mix = mix
+                    .grown(stocks, bonds, cash)
+                    .shifted(&target, &next_target);
+                portfolio_return
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_mix(actual: Mix, stocks: f32, bonds: f32, cash: f32) {
+        assert!(
+            (actual.stocks - stocks).abs() < 0.0001
+                && (actual.bonds - bonds).abs() < 0.0001
+                && (actual.cash - cash).abs() < 0.0001,
+            "{:?}",
+            actual
+        );
+    }
+
+    #[test]
+    fn custom_glide_paths_interpolate_between_breakpoints() {
+        // breakpoints in any order
+        let path = GlidePath::Custom(vec![
+            (60, Mix::normalized(0.4, 0.5, 0.1)),
+            (30, Mix::normalized(1.0, 0.0, 0.0)),
+        ]);
+        assert_mix(path.mix(45, 65), 0.7, 0.25, 0.05);
+        // flat before the first and after the last
+        assert_mix(path.mix(20, 65), 1.0, 0.0, 0.0);
+        assert_mix(path.mix(80, 65), 0.4, 0.5, 0.1);
+        assert_mix(GlidePath::Custom(vec![]).mix(45, 65), 1.0, 0.0, 0.0);
+    }
+
+    #[test]
+    fn target_date_glides_into_retirement() {
+        let path = GlidePath::TargetDate;
+        assert_mix(path.mix(30, 65), 0.9, 0.1, 0.0);
+        assert_mix(path.mix(40, 65), 0.9, 0.1, 0.0);
+        assert_mix(path.mix(55, 65), 0.66, 0.31, 0.03);
+        assert_mix(path.mix(65, 65), 0.5, 0.45, 0.05);
+        assert_mix(path.mix(72, 65), 0.3, 0.65, 0.05);
+        assert_mix(path.mix(90, 65), 0.3, 0.65, 0.05);
+    }
+
+    #[test]
+    fn spans_split_the_run_where_the_glide_path_turns() {
+        assert_eq!(
+            GlidePath::AgeBands.spans(30, 65, 100),
+            vec![(30, 35), (36, 49), (50, 64), (65, 80), (81, 100)]
+        );
+        assert_eq!(
+            GlidePath::TargetDate.spans(45, 65, 100),
+            vec![(45, 64), (65, 71), (72, 100)]
+        );
+        let custom = GlidePath::Custom(vec![
+            (50, Mix::normalized(0.5, 0.5, 0.0)),
+            (20, Mix::normalized(1.0, 0.0, 0.0)),
+        ]);
+        assert_eq!(custom.spans(30, 65, 90), vec![(30, 49), (50, 90)]);
+    }
+
+    #[test]
+    fn rebalancing_holds_the_target_and_drifting_lets_winners_grow() {
+        // stocks double every year while bonds and cash stand still
+        let rates = RatePath {
+            stocks: vec![1.0; 33],
+            bonds: vec![0.0; 33],
+            cash: vec![0.0; 33],
+            ..RatePath::default()
+        };
+        let rebalanced = GlidePath::AgeBands.portfolio_returns(&rates, 30, 65, true);
+        assert!(rebalanced.iter().all(|r| (r - 0.8).abs() < 0.0001));
+        let drifting = GlidePath::AgeBands.portfolio_returns(&rates, 30, 65, false);
+        assert!((drifting[30] - 0.8).abs() < 0.0001);
+        // 1.6 in stocks against 0.2 in bonds
+        assert!((drifting[31] - 1.6 / 1.8).abs() < 0.0001);
+        assert!(drifting[32] > drifting[31]);
+    }
+}
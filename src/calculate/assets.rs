@@ -0,0 +1,247 @@
+use rand::Rng;
+
+use super::{
+    allocation::GlidePath,
+    rates::{standard_normal, RatePath},
+};
+
+pub const ASSETS: usize = 6;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Asset {
+    Stocks,
+    Bonds,
+    Inflation,
+    RentGrowth,
+    HomeGrowth,
+    MortgageRate,
+}
+
+pub const ALL_ASSETS: [Asset; ASSETS] = [
+    Asset::Stocks,
+    Asset::Bonds,
+    Asset::Inflation,
+    Asset::RentGrowth,
+    Asset::HomeGrowth,
+    Asset::MortgageRate,
+];
+
+impl Asset {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Asset::Stocks => "Stocks",
+            Asset::Bonds => "Bonds",
+            Asset::Inflation => "Inflation",
+            Asset::RentGrowth => "Rent Growth",
+            Asset::HomeGrowth => "Home Prices",
+            Asset::MortgageRate => "Mortgage Rates",
+        }
+    }
+}
+
+pub type Matrix = [[f32; ASSETS]; ASSETS];
+
+// lower triangular factor of a symmetric positive definite matrix, None when the matrix isn't one
+pub fn cholesky(matrix: &Matrix) -> Option<Matrix> {
+    let mut factor = [[0.0; ASSETS]; ASSETS];
+    for row in 0..ASSETS {
+        for col in 0..=row {
+            let sum: f32 = (0..col).map(|k| factor[row][k] * factor[col][k]).sum();
+            if row == col {
+                let pivot = matrix[row][row] - sum;
+                if pivot <= 0.0 {
+                    return None;
+                }
+                factor[row][col] = pivot.sqrt();
+            } else {
+                factor[row][col] = (matrix[row][col] - sum) / factor[col][col];
+            }
+        }
+    }
+    Some(factor)
+}
+
+// yearly stock and bond returns, inflation, rent growth and home price growth and mortgage rates drawn together,
+// each follows its mean and standard deviation and the correlations are between each year's shocks
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AssetModel {
+    pub means: [f32; ASSETS],
+    pub std_devs: [f32; ASSETS],
+    // how much of last year's distance from the mean carries into this year, rates and prices move slowly
+    pub persistence: [f32; ASSETS],
+    pub correlations: Matrix,
+}
+
+impl Default for AssetModel {
+    fn default() -> Self {
+        AssetModel {
+            means: [0.10, 0.05, 0.03, 0.03, 0.04, 0.06],
+            std_devs: [0.17, 0.07, 0.015, 0.02, 0.06, 0.015],
+            persistence: [0.0, 0.0, 0.6, 0.5, 0.5, 0.85],
+            correlations: [
+                [1.0, 0.1, -0.1, 0.0, 0.2, -0.1],
+                [0.1, 1.0, -0.3, -0.1, 0.0, -0.5],
+                [-0.1, -0.3, 1.0, 0.7, 0.4, 0.5],
+                [0.0, -0.1, 0.7, 1.0, 0.5, 0.2],
+                [0.2, 0.0, 0.4, 0.5, 1.0, -0.2],
+                [-0.1, -0.5, 0.5, 0.2, -0.2, 1.0],
+            ],
+        }
+    }
+}
+
+impl AssetModel {
+    // sets both sides of a correlation so the matrix stays symmetric
+    pub fn set_correlation(&mut self, a: Asset, b: Asset, correlation: f32) {
+        if a != b {
+            let correlation = correlation.clamp(-1.0, 1.0);
+            self.correlations[a as usize][b as usize] = correlation;
+            self.correlations[b as usize][a as usize] = correlation;
+        }
+    }
+
+    // correlations that can't all hold at once have no cholesky factor
+    pub fn is_consistent(&self) -> bool {
+        cholesky(&self.correlations).is_some()
+    }
+
+    // factor of the correlations, shrunk toward no correlation until they are consistent
+    pub fn correlation_factor(&self) -> Matrix {
+        let mut shrink = 1.0;
+        loop {
+            let mut matrix = self.correlations;
+            for (row, values) in matrix.iter_mut().enumerate() {
+                for (col, value) in values.iter_mut().enumerate() {
+                    *value = if row == col { 1.0 } else { *value * shrink };
+                }
+            }
+            if let Some(factor) = cholesky(&matrix) {
+                return factor;
+            }
+            shrink -= 0.1;
+        }
+    }
+
+    // rates for each age up to the given number of years, savings are split between stocks and bonds by age
+    pub fn rates<R: Rng>(&self, rng: &mut R, years: usize) -> RatePath {
+        let factor = self.correlation_factor();
+        let mut levels = self.means;
+        let mut rates = RatePath::default();
+        for _ in 0..years {
+            let draws: [f32; ASSETS] = std::array::from_fn(|_| standard_normal(rng));
+            for (asset, level) in levels.iter_mut().enumerate() {
+                let shock: f32 = (0..=asset).map(|k| factor[asset][k] * draws[k]).sum();
+                let persistence = self.persistence[asset].clamp(0.0, 0.99);
+                *level = self.means[asset]
+                    + persistence * (*level - self.means[asset])
+                    + (1.0 - persistence.powi(2)).sqrt() * self.std_devs[asset] * shock;
+            }
+            let stocks = levels[Asset::Stocks as usize].max(-1.0);
+            let bonds = levels[Asset::Bonds as usize].max(-1.0);
+            let mortgage = levels[Asset::MortgageRate as usize].max(0.02);
+            rates.inflation.push(levels[Asset::Inflation as usize]);
+            rates.mortgage.push(mortgage);
+            // short rates sit below mortgage rates by the usual spread, adjustable rates reset against them
+            // and cash is held in bills that earn them
+            let short_rate = (mortgage - 0.025).max(0.0);
+            rates.index.push(short_rate);
+            rates.rent_growth.push(levels[Asset::RentGrowth as usize]);
+            rates.home_growth.push(levels[Asset::HomeGrowth as usize]);
+            rates.stocks.push(stocks);
+            rates.bonds.push(bonds);
+            rates.cash.push(short_rate);
+            let (stock_mean, stock_std_dev) = (
+                self.means[Asset::Stocks as usize],
+                self.std_devs[Asset::Stocks as usize],
+            );
+            rates.market_shock.push(if stock_std_dev > 0.0 {
+                (stocks - stock_mean) / stock_std_dev
+            } else {
+                0.0
+            });
+            rates
+                .wage_noise
+                .push(rng.gen_range(-3f32.sqrt()..3f32.sqrt()));
+        }
+        rates.interest = GlidePath::AgeBands.portfolio_returns(&rates, 0, 0, true);
+        rates
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn identity() -> Matrix {
+        std::array::from_fn(|row| std::array::from_fn(|col| if row == col { 1.0 } else { 0.0 }))
+    }
+
+    // the factor times its transpose
+    fn product(factor: &Matrix) -> Matrix {
+        std::array::from_fn(|row| {
+            std::array::from_fn(|col| (0..ASSETS).map(|k| factor[row][k] * factor[col][k]).sum())
+        })
+    }
+
+    fn assert_close(actual: &Matrix, expected: &Matrix) {
+        for row in 0..ASSETS {
+            for col in 0..ASSETS {
+                assert!(
+                    (actual[row][col] - expected[row][col]).abs() < 0.0001,
+                    "{:?} != {:?}",
+                    actual,
+                    expected
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn cholesky_of_the_identity_is_the_identity() {
+        assert_eq!(cholesky(&identity()), Some(identity()));
+    }
+
+    #[test]
+    fn cholesky_factors_a_positive_definite_matrix() {
+        let mut matrix = identity();
+        matrix[0] = [4.0, 2.0, 0.0, 0.0, 0.0, 0.0];
+        matrix[1] = [2.0, 3.0, 0.0, 0.0, 0.0, 0.0];
+        let mut expected = identity();
+        expected[0][0] = 2.0;
+        expected[1] = [1.0, 2f32.sqrt(), 0.0, 0.0, 0.0, 0.0];
+        assert_close(&cholesky(&matrix).unwrap(), &expected);
+    }
+
+    #[test]
+    fn cholesky_rejects_correlations_that_cannot_all_hold() {
+        let mut model = AssetModel {
+            correlations: identity(),
+            ..AssetModel::default()
+        };
+        // stocks can't move with bonds and inflation while those two move against each other
+        model.set_correlation(Asset::Stocks, Asset::Bonds, 0.9);
+        model.set_correlation(Asset::Stocks, Asset::Inflation, 0.9);
+        model.set_correlation(Asset::Bonds, Asset::Inflation, -0.9);
+        assert!(!model.is_consistent());
+        // shrunk until it has a factor
+        let factor = model.correlation_factor();
+        assert!(cholesky(&product(&factor)).is_some());
+    }
+
+    #[test]
+    fn default_correlations_are_consistent() {
+        let model = AssetModel::default();
+        assert!(model.is_consistent());
+        assert_close(&product(&model.correlation_factor()), &model.correlations);
+    }
+
+    #[test]
+    fn correlations_stay_symmetric_and_in_range() {
+        let mut model = AssetModel::default();
+        model.set_correlation(Asset::RentGrowth, Asset::HomeGrowth, 1.5);
+        assert_eq!(model.correlations[3][4], 1.0);
+        assert_eq!(model.correlations[4][3], 1.0);
+        model.set_correlation(Asset::Stocks, Asset::Stocks, 0.5);
+        assert_eq!(model.correlations[0][0], 1.0);
+    }
+}
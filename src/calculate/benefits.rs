@@ -0,0 +1,117 @@
+use super::{household::Household, saver::Saver};
+
+// most social security is taxed as income once other income is counted
+pub const SOCIAL_SECURITY_TAXABLE: f32 = 0.85;
+
+pub trait Benefits<Saver> {
+    fn monthly_social_security(&self) -> f32;
+    fn monthly_pension(&self) -> f32;
+    fn monthly_benefits(&self) -> f32;
+    fn taxable_benefits(&self) -> f32;
+    fn inflate_benefits(&mut self, monthly_inflation: f32);
+}
+
+impl Benefits<Saver<'_>> for Saver<'_> {
+    // paid from the claiming age, a surviving member keeps the larger of the two benefits
+    fn monthly_social_security(&self) -> f32 {
+        let claimed = self.current_age >= self.social_security_age;
+        let own = if claimed {
+            self.social_security_benefit
+        } else {
+            0.0
+        };
+        match &self.partner {
+            None => own,
+            Some(partner) => match (Household::is_alive(self), partner.is_alive()) {
+                (true, true) => own + partner.monthly_social_security(),
+                (true, false) if claimed => own.max(partner.social_security_benefit),
+                (false, true) if partner.current_age >= partner.social_security_age => partner
+                    .social_security_benefit
+                    .max(self.social_security_benefit),
+                _ => 0.0,
+            },
+        }
+    }
+
+    // a pension stops when its member dies
+    fn monthly_pension(&self) -> f32 {
+        let own = if Household::is_alive(self) && self.current_age >= self.pension_age {
+            self.pension_benefit
+        } else {
+            0.0
+        };
+        own + self
+            .partner
+            .as_ref()
+            .map_or(0.0, |partner| partner.monthly_pension())
+    }
+
+    fn monthly_benefits(&self) -> f32 {
+        self.monthly_social_security() + self.monthly_pension()
+    }
+
+    fn taxable_benefits(&self) -> f32 {
+        self.monthly_social_security() * SOCIAL_SECURITY_TAXABLE + self.monthly_pension()
+    }
+
+    // social security always keeps up with inflation, a pension only until it starts paying unless it has a cola
+    fn inflate_benefits(&mut self, monthly_inflation: f32) {
+        self.social_security_benefit *= 1.0 + monthly_inflation;
+        if self.pension_cola || self.current_age < self.pension_age {
+            self.pension_benefit *= 1.0 + monthly_inflation;
+        }
+        if let Some(partner) = self.partner.as_mut() {
+            partner.social_security_benefit *= 1.0 + monthly_inflation;
+            if partner.pension_cola || partner.current_age < partner.pension_age {
+                partner.pension_benefit *= 1.0 + monthly_inflation;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calculate::saver::tests::saver;
+
+    fn retiree(current_age: u8, pension_cola: bool) -> Saver<'static> {
+        Saver {
+            current_age,
+            social_security_age: 67,
+            social_security_benefit: 2000.0,
+            pension_age: 65,
+            pension_benefit: 1000.0,
+            pension_cola,
+            ..saver()
+        }
+    }
+
+    #[test]
+    fn benefits_start_at_their_claiming_ages() {
+        assert_eq!(retiree(64, false).monthly_benefits(), 0.0);
+        assert_eq!(retiree(65, false).monthly_benefits(), 1000.0);
+        assert_eq!(retiree(66, false).monthly_social_security(), 0.0);
+        assert_eq!(retiree(67, false).monthly_benefits(), 3000.0);
+        assert_eq!(retiree(67, false).taxable_benefits(), 2700.0);
+    }
+
+    #[test]
+    fn social_security_keeps_up_with_inflation() {
+        let mut retiree = retiree(70, false);
+        retiree.inflate_benefits(0.01);
+        assert!((retiree.social_security_benefit - 2020.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn pensions_only_grow_after_they_start_with_a_cola() {
+        let mut waiting = retiree(60, false);
+        waiting.inflate_benefits(0.01);
+        assert!((waiting.pension_benefit - 1010.0).abs() < 0.01);
+        let mut fixed = retiree(70, false);
+        fixed.inflate_benefits(0.01);
+        assert_eq!(fixed.pension_benefit, 1000.0);
+        let mut indexed = retiree(70, true);
+        indexed.inflate_benefits(0.01);
+        assert!((indexed.pension_benefit - 1010.0).abs() < 0.01);
+    }
+}
@@ -1,5 +1,5 @@
-use leptos::logging;
 use serde::Serialize;
+use std::num::{ParseFloatError, ParseIntError};
 #[derive(Clone, Copy, Serialize, Debug)]
 pub enum Opts {
     Int(u8),
@@ -21,6 +21,11 @@ impl Opts {
         }
     }
 
+    // an age where 0 means never
+    pub fn get_age(&self) -> Option<u8> {
+        Some(self.get_int()).filter(|age| *age != 0)
+    }
+
     pub fn get_float_ref(&self) -> &f32 {
         match self {
             Opts::Int(_x) => panic!("Can not convert Opt::Int to Opt::Float"),
@@ -28,18 +33,12 @@ impl Opts {
         }
     }
 
-    pub fn opt_from_u8_str(s: &str) -> Self {
-        Opts::Int(s.parse::<u8>().unwrap_or_else(|s| {
-            logging::error!("Error parsing string to u8: {}", s);
-            panic!("Error parsing string to u8")
-        }))
+    pub fn opt_from_u8_str(s: &str) -> Result<Self, ParseIntError> {
+        s.parse::<u8>().map(Opts::Int)
     }
 
-    pub fn opt_from_f32_str(s: &str) -> Self {
-        Opts::Float(s.parse::<f32>().unwrap_or_else(|s| {
-            logging::error!("Error parsing string to f32: {}", s);
-            panic!("Error parsing string to f32")
-        }))
+    pub fn opt_from_f32_str(s: &str) -> Result<Self, ParseFloatError> {
+        s.parse::<f32>().map(Opts::Float)
     }
 }
 
@@ -101,6 +100,126 @@ pub const MORTGAGE_RATES: [Opts; DEATH] = convert_to_float_opts![
     0.093, 0.094, 0.095, 0.096, 0.097, 0.098, 0.099
 ];
 
+pub const APPRECIATION_RATES: [Opts; DEATH] = convert_to_float_opts![
+    -0.050, -0.049, -0.048, -0.047, -0.046, -0.045, -0.044, -0.043, -0.042, -0.041, -0.040, -0.039,
+    -0.038, -0.037, -0.036, -0.035, -0.034, -0.033, -0.032, -0.031, -0.030, -0.029, -0.028, -0.027,
+    -0.026, -0.025, -0.024, -0.023, -0.022, -0.021, -0.020, -0.019, -0.018, -0.017, -0.016, -0.015,
+    -0.014, -0.013, -0.012, -0.011, -0.010, -0.009, -0.008, -0.007, -0.006, -0.005, -0.004, -0.003,
+    -0.002, -0.001, 0.000, 0.001, 0.002, 0.003, 0.004, 0.005, 0.006, 0.007, 0.008, 0.009, 0.010,
+    0.011, 0.012, 0.013, 0.014, 0.015, 0.016, 0.017, 0.018, 0.019, 0.020, 0.021, 0.022, 0.023,
+    0.024, 0.025, 0.026, 0.027, 0.028, 0.029, 0.030, 0.031, 0.032, 0.033, 0.034, 0.035, 0.036,
+    0.037, 0.038, 0.039, 0.040, 0.041, 0.042, 0.043, 0.044, 0.045, 0.046, 0.047, 0.048, 0.049
+];
+
+pub const EXPENSE_CHANGE_RATES: [Opts; DEATH] = convert_to_float_opts![
+    -0.50, -0.49, -0.48, -0.47, -0.46, -0.45, -0.44, -0.43, -0.42, -0.41, -0.40, -0.39, -0.38,
+    -0.37, -0.36, -0.35, -0.34, -0.33, -0.32, -0.31, -0.30, -0.29, -0.28, -0.27, -0.26, -0.25,
+    -0.24, -0.23, -0.22, -0.21, -0.20, -0.19, -0.18, -0.17, -0.16, -0.15, -0.14, -0.13, -0.12,
+    -0.11, -0.10, -0.09, -0.08, -0.07, -0.06, -0.05, -0.04, -0.03, -0.02, -0.01, 0.00, 0.01, 0.02,
+    0.03, 0.04, 0.05, 0.06, 0.07, 0.08, 0.09, 0.10, 0.11, 0.12, 0.13, 0.14, 0.15, 0.16, 0.17, 0.18,
+    0.19, 0.20, 0.21, 0.22, 0.23, 0.24, 0.25, 0.26, 0.27, 0.28, 0.29, 0.30, 0.31, 0.32, 0.33, 0.34,
+    0.35, 0.36, 0.37, 0.38, 0.39, 0.40, 0.41, 0.42, 0.43, 0.44, 0.45, 0.46, 0.47, 0.48, 0.49
+];
+
+pub const RETURN_RATES: [Opts; DEATH] = convert_to_float_opts![
+    -0.200, -0.195, -0.190, -0.185, -0.180, -0.175, -0.170, -0.165, -0.160, -0.155, -0.150, -0.145,
+    -0.140, -0.135, -0.130, -0.125, -0.120, -0.115, -0.110, -0.105, -0.100, -0.095, -0.090, -0.085,
+    -0.080, -0.075, -0.070, -0.065, -0.060, -0.055, -0.050, -0.045, -0.040, -0.035, -0.030, -0.025,
+    -0.020, -0.015, -0.010, -0.005, 0.000, 0.005, 0.010, 0.015, 0.020, 0.025, 0.030, 0.035, 0.040,
+    0.045, 0.050, 0.055, 0.060, 0.065, 0.070, 0.075, 0.080, 0.085, 0.090, 0.095, 0.100, 0.105,
+    0.110, 0.115, 0.120, 0.125, 0.130, 0.135, 0.140, 0.145, 0.150, 0.155, 0.160, 0.165, 0.170,
+    0.175, 0.180, 0.185, 0.190, 0.195, 0.200, 0.205, 0.210, 0.215, 0.220, 0.225, 0.230, 0.235,
+    0.240, 0.245, 0.250, 0.255, 0.260, 0.265, 0.270, 0.275, 0.280, 0.285, 0.290, 0.295
+];
+
+pub const HOME_EXPENSE_RATES: [Opts; DEATH] = convert_to_float_opts![
+    0.000, 0.001, 0.002, 0.003, 0.004, 0.005, 0.006, 0.007, 0.008, 0.009, 0.010, 0.011, 0.012,
+    0.013, 0.014, 0.015, 0.016, 0.017, 0.018, 0.019, 0.020, 0.021, 0.022, 0.023, 0.024, 0.025,
+    0.026, 0.027, 0.028, 0.029, 0.030, 0.031, 0.032, 0.033, 0.034, 0.035, 0.036, 0.037, 0.038,
+    0.039, 0.040, 0.041, 0.042, 0.043, 0.044, 0.045, 0.046, 0.047, 0.048, 0.049, 0.050, 0.051,
+    0.052, 0.053, 0.054, 0.055, 0.056, 0.057, 0.058, 0.059, 0.060, 0.061, 0.062, 0.063, 0.064,
+    0.065, 0.066, 0.067, 0.068, 0.069, 0.070, 0.071, 0.072, 0.073, 0.074, 0.075, 0.076, 0.077,
+    0.078, 0.079, 0.080, 0.081, 0.082, 0.083, 0.084, 0.085, 0.086, 0.087, 0.088, 0.089, 0.090,
+    0.091, 0.092, 0.093, 0.094, 0.095, 0.096, 0.097, 0.098, 0.099
+];
+
+pub const HOME_COST_RANGE: [Opts; DEATH] = convert_to_float_opts![
+    0.0, 10.0, 20.0, 30.0, 40.0, 50.0, 60.0, 70.0, 80.0, 90.0, 100.0, 110.0, 120.0, 130.0, 140.0,
+    150.0, 160.0, 170.0, 180.0, 190.0, 200.0, 210.0, 220.0, 230.0, 240.0, 250.0, 260.0, 270.0,
+    280.0, 290.0, 300.0, 310.0, 320.0, 330.0, 340.0, 350.0, 360.0, 370.0, 380.0, 390.0, 400.0,
+    410.0, 420.0, 430.0, 440.0, 450.0, 460.0, 470.0, 480.0, 490.0, 500.0, 510.0, 520.0, 530.0,
+    540.0, 550.0, 560.0, 570.0, 580.0, 590.0, 600.0, 610.0, 620.0, 630.0, 640.0, 650.0, 660.0,
+    670.0, 680.0, 690.0, 700.0, 710.0, 720.0, 730.0, 740.0, 750.0, 760.0, 770.0, 780.0, 790.0,
+    800.0, 810.0, 820.0, 830.0, 840.0, 850.0, 860.0, 870.0, 880.0, 890.0, 900.0, 910.0, 920.0,
+    930.0, 940.0, 950.0, 960.0, 970.0, 980.0, 990.0
+];
+
+pub const DOWN_PAYMENT_RATES: [Opts; DEATH] = convert_to_float_opts![
+    0.00, 0.01, 0.02, 0.03, 0.04, 0.05, 0.06, 0.07, 0.08, 0.09, 0.10, 0.11, 0.12, 0.13, 0.14, 0.15,
+    0.16, 0.17, 0.18, 0.19, 0.20, 0.21, 0.22, 0.23, 0.24, 0.25, 0.26, 0.27, 0.28, 0.29, 0.30, 0.31,
+    0.32, 0.33, 0.34, 0.35, 0.36, 0.37, 0.38, 0.39, 0.40, 0.41, 0.42, 0.43, 0.44, 0.45, 0.46, 0.47,
+    0.48, 0.49, 0.50, 0.51, 0.52, 0.53, 0.54, 0.55, 0.56, 0.57, 0.58, 0.59, 0.60, 0.61, 0.62, 0.63,
+    0.64, 0.65, 0.66, 0.67, 0.68, 0.69, 0.70, 0.71, 0.72, 0.73, 0.74, 0.75, 0.76, 0.77, 0.78, 0.79,
+    0.80, 0.81, 0.82, 0.83, 0.84, 0.85, 0.86, 0.87, 0.88, 0.89, 0.90, 0.91, 0.92, 0.93, 0.94, 0.95,
+    0.96, 0.97, 0.98, 0.99
+];
+
+pub const WAGE_VOLATILITY_RATES: [Opts; 31] = convert_to_float_opts![
+    0.00, 0.01, 0.02, 0.03, 0.04, 0.05, 0.06, 0.07, 0.08, 0.09, 0.10, 0.11, 0.12, 0.13, 0.14, 0.15,
+    0.16, 0.17, 0.18, 0.19, 0.20, 0.21, 0.22, 0.23, 0.24, 0.25, 0.26, 0.27, 0.28, 0.29, 0.30
+];
+
+pub const CORRELATION_RANGE: [Opts; 41] = convert_to_float_opts![
+    -1.00, -0.95, -0.90, -0.85, -0.80, -0.75, -0.70, -0.65, -0.60, -0.55, -0.50, -0.45, -0.40,
+    -0.35, -0.30, -0.25, -0.20, -0.15, -0.10, -0.05, 0.00, 0.05, 0.10, 0.15, 0.20, 0.25, 0.30,
+    0.35, 0.40, 0.45, 0.50, 0.55, 0.60, 0.65, 0.70, 0.75, 0.80, 0.85, 0.90, 0.95, 1.00
+];
+
+pub const ARM_SPREAD_RATES: [Opts; 41] = convert_to_float_opts![
+    0.0000, 0.0025, 0.0050, 0.0075, 0.0100, 0.0125, 0.0150, 0.0175, 0.0200, 0.0225, 0.0250, 0.0275,
+    0.0300, 0.0325, 0.0350, 0.0375, 0.0400, 0.0425, 0.0450, 0.0475, 0.0500, 0.0525, 0.0550, 0.0575,
+    0.0600, 0.0625, 0.0650, 0.0675, 0.0700, 0.0725, 0.0750, 0.0775, 0.0800, 0.0825, 0.0850, 0.0875,
+    0.0900, 0.0925, 0.0950, 0.0975, 0.1000
+];
+
+pub const STATE_TAX_RATES: [Opts; 61] = convert_to_float_opts![
+    0.0000, 0.0025, 0.0050, 0.0075, 0.0100, 0.0125, 0.0150, 0.0175, 0.0200, 0.0225, 0.0250, 0.0275,
+    0.0300, 0.0325, 0.0350, 0.0375, 0.0400, 0.0425, 0.0450, 0.0475, 0.0500, 0.0525, 0.0550, 0.0575,
+    0.0600, 0.0625, 0.0650, 0.0675, 0.0700, 0.0725, 0.0750, 0.0775, 0.0800, 0.0825, 0.0850, 0.0875,
+    0.0900, 0.0925, 0.0950, 0.0975, 0.1000, 0.1025, 0.1050, 0.1075, 0.1100, 0.1125, 0.1150, 0.1175,
+    0.1200, 0.1225, 0.1250, 0.1275, 0.1300, 0.1325, 0.1350, 0.1375, 0.1400, 0.1425, 0.1450, 0.1475,
+    0.1500
+];
+
+pub const WITHDRAWAL_RATES: [Opts; 41] = convert_to_float_opts![
+    0.0000, 0.0025, 0.0050, 0.0075, 0.0100, 0.0125, 0.0150, 0.0175, 0.0200, 0.0225, 0.0250, 0.0275,
+    0.0300, 0.0325, 0.0350, 0.0375, 0.0400, 0.0425, 0.0450, 0.0475, 0.0500, 0.0525, 0.0550, 0.0575,
+    0.0600, 0.0625, 0.0650, 0.0675, 0.0700, 0.0725, 0.0750, 0.0775, 0.0800, 0.0825, 0.0850, 0.0875,
+    0.0900, 0.0925, 0.0950, 0.0975, 0.1000
+];
+
+pub const RETURN_STD_DEV_RATES: [Opts; 51] = convert_to_float_opts![
+    0.00, 0.01, 0.02, 0.03, 0.04, 0.05, 0.06, 0.07, 0.08, 0.09, 0.10, 0.11, 0.12, 0.13, 0.14, 0.15,
+    0.16, 0.17, 0.18, 0.19, 0.20, 0.21, 0.22, 0.23, 0.24, 0.25, 0.26, 0.27, 0.28, 0.29, 0.30, 0.31,
+    0.32, 0.33, 0.34, 0.35, 0.36, 0.37, 0.38, 0.39, 0.40, 0.41, 0.42, 0.43, 0.44, 0.45, 0.46, 0.47,
+    0.48, 0.49, 0.50
+];
+
+pub const CHANCE_RATES: [Opts; 101] = convert_to_float_opts![
+    0.00, 0.01, 0.02, 0.03, 0.04, 0.05, 0.06, 0.07, 0.08, 0.09, 0.10, 0.11, 0.12, 0.13, 0.14, 0.15,
+    0.16, 0.17, 0.18, 0.19, 0.20, 0.21, 0.22, 0.23, 0.24, 0.25, 0.26, 0.27, 0.28, 0.29, 0.30, 0.31,
+    0.32, 0.33, 0.34, 0.35, 0.36, 0.37, 0.38, 0.39, 0.40, 0.41, 0.42, 0.43, 0.44, 0.45, 0.46, 0.47,
+    0.48, 0.49, 0.50, 0.51, 0.52, 0.53, 0.54, 0.55, 0.56, 0.57, 0.58, 0.59, 0.60, 0.61, 0.62, 0.63,
+    0.64, 0.65, 0.66, 0.67, 0.68, 0.69, 0.70, 0.71, 0.72, 0.73, 0.74, 0.75, 0.76, 0.77, 0.78, 0.79,
+    0.80, 0.81, 0.82, 0.83, 0.84, 0.85, 0.86, 0.87, 0.88, 0.89, 0.90, 0.91, 0.92, 0.93, 0.94, 0.95,
+    0.96, 0.97, 0.98, 0.99, 1.00
+];
+
+pub const ALLOCATION_SHARES: [Opts; 21] = convert_to_float_opts![
+    0.00, 0.05, 0.10, 0.15, 0.20, 0.25, 0.30, 0.35, 0.40, 0.45, 0.50, 0.55, 0.60, 0.65, 0.70, 0.75,
+    0.80, 0.85, 0.90, 0.95, 1.00
+];
+
 pub const INCEXP_RANGE: [Opts; DEATH] = convert_to_float_opts![
     0.0, 100.0, 250.0, 500.0, 1000.0, 1500.0, 2000.0, 2500.0, 3000.0, 3500.0, 4000.0, 4500.0,
     5000.0, 5500.0, 6000.0, 6500.0, 7000.0, 7500.0, 8000.0, 8500.0, 9000.0, 9500.0, 10000.0,
@@ -231,3 +350,14 @@ pub const YAXIS_BUCKETS: [Opts; DEATH] = convert_to_float_opts![
     1960000000.0,
     1980000000.0
 ];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn age_of_zero_means_never() {
+        assert_eq!(Opts::Int(0).get_age(), None);
+        assert_eq!(Opts::Int(55).get_age(), Some(55));
+    }
+}
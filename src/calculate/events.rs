@@ -0,0 +1,201 @@
+use super::{household::Household, saver::Saver};
+
+// dated changes to a scenario, amounts are in today's dollars and spans run from the start age up to the end age
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LifeEvent {
+    // one off inflow, or outflow when negative, at the start of the year
+    Windfall {
+        age: u8,
+        amount: f32,
+    },
+    // income changes by the percent over the span
+    IncomeChange {
+        start_age: u8,
+        end_age: u8,
+        change: f32,
+    },
+    // a new recurring monthly expense like childcare or tuition over the span
+    Expense {
+        start_age: u8,
+        end_age: u8,
+        monthly: f32,
+    },
+    // no income over the span
+    Unemployment {
+        start_age: u8,
+        end_age: u8,
+    },
+}
+
+impl LifeEvent {
+    pub fn start_age(&self) -> u8 {
+        match self {
+            LifeEvent::Windfall { age, .. } => *age,
+            LifeEvent::IncomeChange { start_age, .. }
+            | LifeEvent::Expense { start_age, .. }
+            | LifeEvent::Unemployment { start_age, .. } => *start_age,
+        }
+    }
+
+    pub fn end_age(&self) -> u8 {
+        match self {
+            LifeEvent::Windfall { age, .. } => *age,
+            LifeEvent::IncomeChange { end_age, .. }
+            | LifeEvent::Expense { end_age, .. }
+            | LifeEvent::Unemployment { end_age, .. } => *end_age,
+        }
+    }
+
+    pub fn is_active(&self, age: u8) -> bool {
+        match self {
+            LifeEvent::Windfall { age: event_age, .. } => *event_age == age,
+            LifeEvent::IncomeChange {
+                start_age, end_age, ..
+            }
+            | LifeEvent::Expense {
+                start_age, end_age, ..
+            }
+            | LifeEvent::Unemployment { start_age, end_age } => {
+                (*start_age..*end_age).contains(&age)
+            }
+        }
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            LifeEvent::Windfall { amount, .. } if *amount < 0.0 => "Outflow".to_string(),
+            LifeEvent::Windfall { .. } => "Windfall".to_string(),
+            LifeEvent::IncomeChange { change, .. } => {
+                format!("Income {:+}%", (change * 100.0).round())
+            }
+            LifeEvent::Expense { .. } => "Expense".to_string(),
+            LifeEvent::Unemployment { .. } => "Unemployed".to_string(),
+        }
+    }
+}
+
+pub trait LifeEvents<Saver> {
+    fn monthly_member_wages(&self) -> [f32; 2];
+    fn monthly_wages(&self) -> f32;
+    fn monthly_event_expenses(&self) -> f32;
+    fn receive_windfalls(&mut self);
+}
+
+impl LifeEvents<Saver<'_>> for Saver<'_> {
+    // the saver's and the partner's wages after any raises, pay cuts and unemployment this year,
+    // events follow the saver's age and only change the saver's wages
+    fn monthly_member_wages(&self) -> [f32; 2] {
+        let wages = if Household::is_working(self) {
+            self.monthly_income
+        } else {
+            0.0
+        };
+        let wages = self
+            .life_events
+            .iter()
+            .filter(|event| event.is_active(self.current_age))
+            .fold(wages, |wages, event| match event {
+                LifeEvent::IncomeChange { change, .. } => wages * (1.0 + change),
+                LifeEvent::Unemployment { .. } => 0.0,
+                _ => wages,
+            });
+        [wages, Household::partner_wages(self)]
+    }
+
+    // household income
+    fn monthly_wages(&self) -> f32 {
+        self.monthly_member_wages().iter().sum()
+    }
+
+    fn monthly_event_expenses(&self) -> f32 {
+        self.life_events
+            .iter()
+            .filter(|event| event.is_active(self.current_age))
+            .map(|event| match event {
+                LifeEvent::Expense { monthly, .. } => monthly * self.price_level,
+                _ => 0.0,
+            })
+            .sum()
+    }
+
+    fn receive_windfalls(&mut self) {
+        for event in &self.life_events {
+            if let LifeEvent::Windfall { age, amount } = event {
+                if *age == self.current_age {
+                    self.total_savings += amount * self.price_level;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calculate::saver::tests::saver;
+
+    fn with_event(event: LifeEvent, current_age: u8) -> Saver<'static> {
+        Saver {
+            current_age,
+            life_events: vec![event],
+            ..saver()
+        }
+    }
+
+    #[test]
+    fn windfalls_arrive_once_at_their_age_in_todays_dollars() {
+        let windfall = LifeEvent::Windfall {
+            age: 40,
+            amount: 50000.0,
+        };
+        for (age, received) in [(39, 0.0), (40, 100000.0), (41, 0.0)] {
+            let mut saver = Saver {
+                price_level: 2.0,
+                ..with_event(windfall, age)
+            };
+            saver.receive_windfalls();
+            assert_eq!(saver.total_savings, 200000.0 + received, "age {}", age);
+        }
+    }
+
+    #[test]
+    fn income_changes_apply_from_the_start_age_up_to_the_end_age() {
+        let raise = LifeEvent::IncomeChange {
+            start_age: 40,
+            end_age: 45,
+            change: 0.5,
+        };
+        assert_eq!(with_event(raise, 39).monthly_wages(), 14000.0);
+        assert_eq!(with_event(raise, 40).monthly_wages(), 21000.0);
+        assert_eq!(with_event(raise, 44).monthly_wages(), 21000.0);
+        assert_eq!(with_event(raise, 45).monthly_wages(), 14000.0);
+    }
+
+    #[test]
+    fn expenses_are_charged_over_their_span_in_todays_dollars() {
+        let childcare = LifeEvent::Expense {
+            start_age: 32,
+            end_age: 37,
+            monthly: 1500.0,
+        };
+        assert_eq!(with_event(childcare, 31).monthly_event_expenses(), 0.0);
+        let saver = Saver {
+            price_level: 1.5,
+            ..with_event(childcare, 32)
+        };
+        assert_eq!(saver.monthly_event_expenses(), 2250.0);
+        assert_eq!(with_event(childcare, 37).monthly_event_expenses(), 0.0);
+    }
+
+    #[test]
+    fn unemployment_stops_wages_over_its_span() {
+        let layoff = LifeEvent::Unemployment {
+            start_age: 50,
+            end_age: 51,
+        };
+        assert_eq!(with_event(layoff, 49).monthly_wages(), 14000.0);
+        assert_eq!(with_event(layoff, 50).monthly_wages(), 0.0);
+        assert_eq!(with_event(layoff, 51).monthly_wages(), 14000.0);
+        assert_eq!(layoff.label(), "Unemployed");
+    }
+}
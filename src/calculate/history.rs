@@ -0,0 +1,184 @@
+use super::{allocation::GlidePath, rates::RatePath};
+
+pub const HISTORY_START_YEAR: u16 = 1928;
+
+// annual s&p 500 total return, 10 year treasury total return, 3 month treasury bill return and cpi inflation
+// (december to december) for each year from 1928 (damodaran's historical returns and the bls cpi-u)
+pub const HISTORICAL_RETURNS: [(f32, f32, f32, f32); 96] = [
+    (0.4381, 0.0084, 0.0308, -0.0116),
+    (-0.0830, 0.0420, 0.0316, 0.0058),
+    (-0.2512, 0.0454, 0.0455, -0.0640),
+    (-0.4384, -0.0256, 0.0231, -0.0932),
+    (-0.0864, 0.0879, 0.0107, -0.1027),
+    (0.4998, 0.0186, 0.0096, 0.0076),
+    (-0.0119, 0.0796, 0.0028, 0.0152),
+    (0.4674, 0.0447, 0.0017, 0.0299),
+    (0.3194, 0.0502, 0.0017, 0.0145),
+    (-0.3534, 0.0138, 0.0028, 0.0286),
+    (0.2928, 0.0421, 0.0007, -0.0278),
+    (-0.0110, 0.0441, 0.0005, 0.0000),
+    (-0.1067, 0.0540, 0.0004, 0.0071),
+    (-0.1277, -0.0202, 0.0013, 0.0993),
+    (0.1917, 0.0229, 0.0034, 0.0903),
+    (0.2506, 0.0249, 0.0038, 0.0296),
+    (0.1903, 0.0258, 0.0038, 0.0230),
+    (0.3582, 0.0380, 0.0038, 0.0225),
+    (-0.0843, 0.0313, 0.0038, 0.1813),
+    (0.0520, 0.0092, 0.0060, 0.0884),
+    (0.0570, 0.0195, 0.0105, 0.0299),
+    (0.1830, 0.0466, 0.0112, -0.0207),
+    (0.3081, 0.0043, 0.0120, 0.0593),
+    (0.2368, -0.0030, 0.0152, 0.0600),
+    (0.1815, 0.0227, 0.0172, 0.0075),
+    (-0.0121, 0.0414, 0.0189, 0.0075),
+    (0.5256, 0.0329, 0.0094, -0.0074),
+    (0.3260, -0.0134, 0.0172, 0.0037),
+    (0.0744, -0.0226, 0.0262, 0.0299),
+    (-0.1046, 0.0680, 0.0322, 0.0290),
+    (0.4372, -0.0210, 0.0177, 0.0176),
+    (0.1206, -0.0265, 0.0339, 0.0173),
+    (0.0034, 0.1164, 0.0288, 0.0136),
+    (0.2664, 0.0206, 0.0235, 0.0067),
+    (-0.0881, 0.0569, 0.0277, 0.0133),
+    (0.2261, 0.0168, 0.0316, 0.0164),
+    (0.1642, 0.0373, 0.0355, 0.0097),
+    (0.1240, 0.0072, 0.0395, 0.0192),
+    (-0.0997, 0.0291, 0.0486, 0.0346),
+    (0.2380, -0.0158, 0.0429, 0.0304),
+    (0.1081, 0.0327, 0.0534, 0.0472),
+    (-0.0824, -0.0501, 0.0667, 0.0620),
+    (0.0356, 0.1675, 0.0639, 0.0557),
+    (0.1422, 0.0979, 0.0433, 0.0327),
+    (0.1876, 0.0282, 0.0406, 0.0341),
+    (-0.1431, 0.0366, 0.0704, 0.0871),
+    (-0.2590, 0.0199, 0.0785, 0.1234),
+    (0.3700, 0.0361, 0.0579, 0.0694),
+    (0.2383, 0.1598, 0.0498, 0.0486),
+    (-0.0698, 0.0129, 0.0527, 0.0670),
+    (0.0651, -0.0078, 0.0719, 0.0902),
+    (0.1852, 0.0067, 0.1007, 0.1329),
+    (0.3174, -0.0299, 0.1143, 0.1252),
+    (-0.0470, 0.0820, 0.1403, 0.0892),
+    (0.2042, 0.3281, 0.1061, 0.0383),
+    (0.2234, 0.0320, 0.0861, 0.0379),
+    (0.0615, 0.1373, 0.0952, 0.0395),
+    (0.3124, 0.2571, 0.0748, 0.0380),
+    (0.1849, 0.2428, 0.0598, 0.0110),
+    (0.0581, -0.0496, 0.0578, 0.0443),
+    (0.1654, 0.0822, 0.0667, 0.0442),
+    (0.3148, 0.1769, 0.0811, 0.0465),
+    (-0.0306, 0.0624, 0.0749, 0.0611),
+    (0.3023, 0.1500, 0.0538, 0.0306),
+    (0.0749, 0.0936, 0.0343, 0.0290),
+    (0.0997, 0.1421, 0.0300, 0.0275),
+    (0.0133, -0.0804, 0.0425, 0.0267),
+    (0.3720, 0.2348, 0.0549, 0.0254),
+    (0.2268, 0.0143, 0.0501, 0.0332),
+    (0.3310, 0.0994, 0.0506, 0.0170),
+    (0.2834, 0.1492, 0.0478, 0.0161),
+    (0.2089, -0.0825, 0.0464, 0.0268),
+    (-0.0903, 0.1666, 0.0582, 0.0339),
+    (-0.1185, 0.0557, 0.0340, 0.0155),
+    (-0.2197, 0.1512, 0.0161, 0.0238),
+    (0.2836, 0.0038, 0.0101, 0.0188),
+    (0.1074, 0.0449, 0.0137, 0.0326),
+    (0.0483, 0.0287, 0.0315, 0.0342),
+    (0.1561, 0.0196, 0.0473, 0.0254),
+    (0.0548, 0.1021, 0.0436, 0.0408),
+    (-0.3655, 0.2010, 0.0137, 0.0009),
+    (0.2594, -0.1112, 0.0015, 0.0272),
+    (0.1482, 0.0846, 0.0014, 0.0150),
+    (0.0210, 0.1604, 0.0005, 0.0296),
+    (0.1589, 0.0297, 0.0009, 0.0174),
+    (0.3215, -0.0910, 0.0006, 0.0150),
+    (0.1352, 0.1075, 0.0003, 0.0076),
+    (0.0138, 0.0128, 0.0005, 0.0073),
+    (0.1177, 0.0069, 0.0032, 0.0207),
+    (0.2161, 0.0280, 0.0093, 0.0211),
+    (-0.0423, -0.0002, 0.0194, 0.0191),
+    (0.3121, 0.0964, 0.0206, 0.0229),
+    (0.1802, 0.1133, 0.0035, 0.0136),
+    (0.2847, -0.0442, 0.0005, 0.0704),
+    (-0.1804, -0.1783, 0.0202, 0.0645),
+    (0.2606, 0.0388, 0.0507, 0.0335),
+];
+
+// start years whose whole run fits in the history, none when the run is longer than the history
+pub fn start_years(run_years: usize) -> Vec<u16> {
+    let starts = (HISTORICAL_RETURNS.len() + 1).saturating_sub(run_years.max(1));
+    (0..starts as u16)
+        .map(|offset| HISTORY_START_YEAR + offset)
+        .collect()
+}
+
+// replay history from the start year in the first year after the current age, earlier ages repeat the start year,
+// the run has to fit in the history from the start year
+pub fn historical_rates(start_year: u16, current_age: u8, years: usize) -> RatePath {
+    let count = HISTORICAL_RETURNS.len() as f32;
+    let mean = HISTORICAL_RETURNS
+        .iter()
+        .map(|(stocks, ..)| stocks)
+        .sum::<f32>()
+        / count;
+    let std_dev = (HISTORICAL_RETURNS
+        .iter()
+        .map(|(stocks, ..)| (stocks - mean).powi(2))
+        .sum::<f32>()
+        / count)
+        .sqrt();
+
+    let mut rates = RatePath::default();
+    for age in 0..years {
+        let offset = age.saturating_sub(current_age as usize + 1);
+        let year = (start_year - HISTORY_START_YEAR) as usize + offset;
+        let (stocks, bonds, bills, inflation) = HISTORICAL_RETURNS[year];
+        rates.inflation.push(inflation);
+        // no history of mortgage rates this far back, so the same real rates the simulation centers on
+        rates.mortgage.push((0.03 + inflation).max(0.02));
+        rates.index.push((0.005 + inflation).max(0.0));
+        rates.market_shock.push((stocks - mean) / std_dev);
+        rates.wage_noise.push(0.0);
+        rates.stocks.push(stocks);
+        rates.bonds.push(bonds);
+        // cash is held in bills
+        rates.cash.push(bills);
+    }
+    rates.interest = GlidePath::AgeBands.portfolio_returns(&rates, 0, 0, true);
+    rates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calculate::consts::DEATH;
+
+    #[test]
+    fn history_runs_from_1928_through_2023() {
+        assert_eq!(
+            HISTORY_START_YEAR as usize + HISTORICAL_RETURNS.len() - 1,
+            2023
+        );
+    }
+
+    #[test]
+    fn start_years_only_include_runs_that_fit() {
+        let years = start_years(30);
+        assert_eq!(years.first(), Some(&1928));
+        assert_eq!(years.last(), Some(&1994));
+        assert_eq!(start_years(96), vec![1928]);
+        assert!(start_years(97).is_empty());
+        assert_eq!(start_years(0).len(), 96);
+    }
+
+    #[test]
+    fn historical_rates_replay_history_after_the_current_age() {
+        // a 30 year old's run from 31 up to 99 takes the 69 years from 1955 through 2023
+        let rates = historical_rates(1955, 30, DEATH);
+        let (.., first) = HISTORICAL_RETURNS[1955 - 1928];
+        let (.., last) = HISTORICAL_RETURNS[95];
+        assert_eq!(rates.inflation.len(), DEATH);
+        assert_eq!(rates.inflation[0], first);
+        assert_eq!(rates.inflation[31], first);
+        assert_eq!(rates.inflation[DEATH - 1], last);
+    }
+}
@@ -0,0 +1,103 @@
+use super::{consts::DEATH, saver::Saver, wages::Wages};
+
+// the second earner in a two person household, savings and housing are shared with the saver
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Partner {
+    pub current_age: u8,
+    pub retirement_age: u8,
+    pub monthly_income: f32,
+    // monthly benefits grown with inflation, paid from their starting ages
+    pub social_security_age: u8,
+    pub social_security_benefit: f32,
+    pub pension_age: u8,
+    pub pension_benefit: f32,
+    pub pension_cola: bool,
+}
+
+impl Partner {
+    pub fn is_alive(&self) -> bool {
+        (self.current_age as usize) < DEATH
+    }
+
+    pub fn is_working(&self) -> bool {
+        self.is_alive() && self.current_age < self.retirement_age
+    }
+
+    pub fn monthly_social_security(&self) -> f32 {
+        if self.is_alive() && self.current_age >= self.social_security_age {
+            self.social_security_benefit
+        } else {
+            0.0
+        }
+    }
+
+    pub fn monthly_pension(&self) -> f32 {
+        if self.is_alive() && self.current_age >= self.pension_age {
+            self.pension_benefit
+        } else {
+            0.0
+        }
+    }
+}
+
+pub trait Household<Saver> {
+    fn is_alive(&self) -> bool;
+    fn is_working(&self) -> bool;
+    fn partner_wages(&self) -> f32;
+    fn is_retired(&self) -> bool;
+    fn last_age(&self, death_age: u8) -> u8;
+    fn grow_partner_income(&mut self, monthly_inflation: f32);
+    fn age_partner(&mut self);
+}
+
+impl Household<Saver<'_>> for Saver<'_> {
+    fn is_alive(&self) -> bool {
+        (self.current_age as usize) < DEATH
+    }
+
+    fn is_working(&self) -> bool {
+        self.is_alive() && self.current_age < self.retirement_age
+    }
+
+    fn partner_wages(&self) -> f32 {
+        match &self.partner {
+            Some(partner) if partner.is_working() => partner.monthly_income,
+            _ => 0.0,
+        }
+    }
+
+    // the household lives off savings once no one is working any more
+    fn is_retired(&self) -> bool {
+        !self.is_working()
+            && !self
+                .partner
+                .as_ref()
+                .is_some_and(|partner| partner.is_working())
+    }
+
+    // the run goes on until the younger member reaches the death age
+    fn last_age(&self, death_age: u8) -> u8 {
+        match &self.partner {
+            Some(partner) => {
+                death_age.saturating_add(self.current_age.saturating_sub(partner.current_age))
+            }
+            None => death_age,
+        }
+    }
+
+    // the partner's income follows the same wage growth profile at their own age and the same yearly shock
+    fn grow_partner_income(&mut self, monthly_inflation: f32) {
+        let shock = self.wage_shock();
+        if let Some(partner) = self.partner.as_mut() {
+            partner.monthly_income *= 1.0
+                + monthly_inflation
+                + (self.wage_growth.growth(partner.current_age) + shock) / 12.0;
+        }
+    }
+
+    fn age_partner(&mut self) {
+        if let Some(partner) = self.partner.as_mut() {
+            partner.current_age = partner.current_age.saturating_add(1);
+        }
+    }
+}
@@ -1,4 +1,16 @@
+pub mod accounts;
+pub mod allocation;
+pub mod assets;
+pub mod benefits;
 pub mod consts;
+pub mod events;
+pub mod history;
+pub mod household;
 pub mod owner;
 pub mod rates;
 pub mod saver;
+pub mod simulation;
+pub mod spending;
+pub mod tax;
+pub mod wages;
+pub mod withdrawal;
@@ -1,24 +1,68 @@
-use super::saver::Saver;
+use super::{saver::Saver, tax::Taxpayer};
 
-pub const PROPERTY_TAX: f32 = 0.01 / 12.0;
+// lenders only charge pmi on loans over this share of the price, less than 20% down
+pub const PMI_MAX_LTV: f32 = 0.80;
+
+// monthly home costs beyond the mortgage, one line per cost
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct HomeCosts {
+    pub property_tax: f32,
+    pub maintenance: f32,
+    pub hoa: f32,
+    pub insurance: f32,
+    pub pmi: f32,
+}
+
+impl HomeCosts {
+    pub fn total(&self) -> f32 {
+        self.property_tax + self.maintenance + self.hoa + self.insurance + self.pmi
+    }
+}
+
+// adjustable rate mortgage, fixed for the first years then reset every year to the index plus the margin
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Arm {
+    pub fixed_years: u8,
+    pub margin: f32,
+    pub periodic_cap: f32,
+    pub lifetime_cap: f32,
+    // rate the loan started at, set when the home is bought
+    pub initial_rate: f32,
+}
 
 pub trait Owner<Saver> {
     fn monthly_mortgage_rate(&self) -> f32;
     fn mortgage_term_months(&self) -> f32;
     fn mortgage_installments(&self) -> f32;
     fn monthly_mortgage_interest_payment(&self) -> f32;
+    fn loan_to_value(&self) -> f32;
+    fn monthly_pmi(&self) -> f32;
+    fn start_pmi(&mut self);
+    fn monthly_home_costs(&self) -> HomeCosts;
     fn monthly_home_expenses(&self) -> f32;
+    fn monthly_home_appreciation(&self) -> f32;
+    fn own_home(&mut self);
+    fn buy_home(&mut self);
+    fn can_afford_purchase(&self) -> bool;
+    fn purchase_home(&mut self);
+    fn sell_home(&mut self);
+    fn should_refinance(&self) -> bool;
+    fn refinance(&mut self);
+    fn prepay_principal(&mut self, amount: f32);
+    fn should_reset_arm(&self) -> bool;
+    fn reset_arm(&mut self);
     fn expenses(&mut self) -> f32;
 }
 
-impl Owner<Saver> for super::saver::Saver {
+impl Owner<Saver<'_>> for super::saver::Saver<'_> {
     // calculate monthly mortgage rate
     fn monthly_mortgage_rate(&self) -> f32 {
         self.mortgage_rate / 12.0
     }
-    // calculate mortgage term in months
+    // calculate remaining mortgage term in months since the current amortization started
     fn mortgage_term_months(&self) -> f32 {
-        self.mortgage_term as f32 * 12.0
+        let elapsed = self.current_age.saturating_sub(self.mortgage_start_age) as f32;
+        ((self.mortgage_term as f32 - elapsed) * 12.0).max(12.0)
     }
     // calculate monthly mortgage payment
     fn mortgage_installments(&self) -> f32 {
@@ -39,22 +83,510 @@ impl Owner<Saver> for super::saver::Saver {
         self.mortgage_debt * self.monthly_mortgage_rate()
     }
 
+    // mortgage debt over the current (appreciated) home value
+    fn loan_to_value(&self) -> f32 {
+        if self.home_value > 0.0 {
+            self.mortgage_debt / self.home_value
+        } else {
+            0.0
+        }
+    }
+
+    // private mortgage insurance on the debt until the loan to value first drops to the cutoff
+    fn monthly_pmi(&self) -> f32 {
+        if self.pmi_end_age.is_none() && self.loan_to_value() > self.pmi_ltv_cutoff {
+            self.mortgage_debt * (self.pmi_rate / 12.0)
+        } else {
+            0.0
+        }
+    }
+
+    // a new loan only pays pmi with less than 20% down
+    fn start_pmi(&mut self) {
+        self.pmi_end_age = if self.loan_to_value() > PMI_MAX_LTV {
+            None
+        } else {
+            Some(self.current_age)
+        };
+    }
+
+    // property tax and maintenance follow the home value, hoa and insurance are inflated dollar amounts
+    fn monthly_home_costs(&self) -> HomeCosts {
+        HomeCosts {
+            property_tax: self.home_value * (self.property_tax_rate / 12.0),
+            maintenance: self.home_value * (self.maintenance_rate / 12.0),
+            // dues and insurance are only paid while a home is owned
+            hoa: if self.home_value > 0.0 {
+                self.monthly_hoa
+            } else {
+                0.0
+            },
+            insurance: if self.home_value > 0.0 {
+                self.monthly_insurance
+            } else {
+                0.0
+            },
+            pmi: self.monthly_pmi(),
+        }
+    }
+
     fn monthly_home_expenses(&self) -> f32 {
-        self.home_value * (self.home_expenses / 12.0)
+        self.monthly_home_costs().total()
+    }
+
+    fn monthly_home_appreciation(&self) -> f32 {
+        self.home_value * self.monthly_home_growth()
+    }
+
+    // a home already owned at the start of the run, its closing costs were paid when it was bought
+    fn own_home(&mut self) {
+        self.home_cost_basis = self.home_value;
+        self.start_pmi();
+        if let Some(arm) = self.arm.as_mut() {
+            arm.initial_rate = self.mortgage_rate;
+        }
+    }
+
+    // purchase event, the down payment is already home equity so only closing costs and points leave savings
+    fn buy_home(&mut self) {
+        let closing_costs = self.home_value * self.closing_cost_rate;
+        self.total_savings -= closing_costs;
+        self.own_home();
+        self.home_cost_basis += closing_costs;
+    }
+
+    // liquid savings have to cover the down payment and closing costs
+    fn can_afford_purchase(&self) -> bool {
+        self.liquid_assets()
+            >= self.purchase_home_value * (self.down_payment_rate + self.closing_cost_rate)
+    }
+
+    // buy later event, stop renting and take a mortgage at the market rate on the inflated purchase price
+    // after the down payment
+    fn purchase_home(&mut self) {
+        self.home_value = self.purchase_home_value;
+        self.mortgage_debt = self.purchase_home_value * (1.0 - self.down_payment_rate);
+        self.mortgage_rate = self.mortgage_rates[self.current_age as usize];
+        self.monthly_rent = 0.0;
+        self.mortgage_start_age = self.current_age;
+        self.cached_mortgage_installment = Some(self.mortgage_installments());
+        self.home_owned_age = if self.mortgage_debt == 0.0 {
+            Some(self.current_age)
+        } else {
+            None
+        };
+        self.buy_home();
+    }
+
+    // sale event, pay off the mortgage from the proceeds and keep the equity minus selling costs as liquid savings
+    // then move back to renting at the inflated rent, the gain over the primary residence exclusion is taxed
+    fn sell_home(&mut self) {
+        let proceeds = self.home_value * (1.0 - self.selling_cost_rate);
+        let gain = proceeds - self.home_cost_basis - self.home_sale_exclusion;
+        self.total_savings -= self.home_value * self.selling_cost_rate;
+        self.total_savings -= self.capital_gains_tax(gain);
+        self.home_value = 0.0;
+        self.mortgage_debt = 0.0;
+        self.cached_mortgage_installment = None;
+        self.monthly_rent = self.rent_after_sale;
+    }
+
+    // refinance at the scheduled age, or whenever the market rate falls far enough below the current rate
+    fn should_refinance(&self) -> bool {
+        if self.mortgage_debt <= 0.0 {
+            return false;
+        }
+        let market_rate = self.mortgage_rates[self.current_age as usize];
+        self.refinance_age == Some(self.current_age)
+            || self
+                .refinance_rate_drop
+                .is_some_and(|drop| market_rate <= self.mortgage_rate - drop)
     }
 
-    // calculate monthly expenses for a homeowner (mortgage, property tax, home expenses) + other
+    // re-amortize the remaining debt at the market rate over the new term and pay the closing costs
+    // (an adjustable rate mortgage is refinanced into a fixed rate)
+    fn refinance(&mut self) {
+        self.total_savings -= self.mortgage_debt * self.refinance_cost_rate;
+        self.mortgage_rate = self.mortgage_rates[self.current_age as usize];
+        self.mortgage_term = self.refinance_term;
+        self.mortgage_start_age = self.current_age;
+        self.arm = None;
+        self.cached_mortgage_installment = Some(self.mortgage_installments());
+    }
+
+    // an adjustable rate resets every year once the fixed period is over
+    fn should_reset_arm(&self) -> bool {
+        match self.arm {
+            Some(arm) => {
+                self.mortgage_debt > 0.0
+                    && self.current_age >= self.mortgage_start_age.saturating_add(arm.fixed_years)
+            }
+            None => false,
+        }
+    }
+
+    // move the rate to the index plus the margin within the caps and re-amortize over the remaining term
+    fn reset_arm(&mut self) {
+        if let Some(arm) = self.arm {
+            let index_rate = self.index_rates[self.current_age as usize] + arm.margin;
+            // negative or missing caps hold the rate where it is
+            let periodic_cap = arm.periodic_cap.max(0.0);
+            let lifetime_cap = arm.lifetime_cap.max(0.0);
+            self.mortgage_rate = index_rate
+                .max(self.mortgage_rate - periodic_cap)
+                .min(self.mortgage_rate + periodic_cap)
+                .min(arm.initial_rate + lifetime_cap)
+                .max(arm.margin);
+            self.cached_mortgage_installment = Some(self.mortgage_installments());
+        }
+    }
+
+    // pay down extra principal out of liquid savings, never more than the remaining debt
+    fn prepay_principal(&mut self, amount: f32) {
+        let amount = amount
+            .min(self.mortgage_debt)
+            .min(self.liquid_assets())
+            .max(0.0);
+        self.mortgage_debt -= amount;
+    }
+
+    // calculate monthly expenses for a homeowner (mortgage interest + home costs)
     fn expenses(&mut self) -> f32 {
         let mortgage_interest = self.monthly_mortgage_interest_payment();
         let monthly_principle = self.cached_mortgage_installment.unwrap_or(0.0) - mortgage_interest;
-        let monthly_expenses =
-            self.home_value * PROPERTY_TAX + self.monthly_home_expenses() + mortgage_interest;
-        // make a mortgage payment if you have a mortgage
+        let monthly_expenses = self.monthly_home_expenses() + mortgage_interest;
+        // pmi drops for good once paydown or appreciation brings the loan to value under the cutoff
+        if self.pmi_end_age.is_none() && self.loan_to_value() <= self.pmi_ltv_cutoff {
+            self.pmi_end_age = Some(self.current_age);
+        }
+        // make a mortgage payment (plus any extra principal) if you have a mortgage
         if self.mortgage_debt > 0.0 {
-            self.mortgage_debt -= monthly_principle;
+            self.mortgage_interest_paid += mortgage_interest;
+            self.mortgage_debt -= monthly_principle.min(self.mortgage_debt);
+            self.prepay_principal(self.extra_monthly_principal);
         } else if self.home_owned_age.is_none() {
             self.home_owned_age = Some(self.current_age);
         }
         monthly_expenses
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calculate::{
+        consts::DEATH,
+        saver::tests::saver,
+        tax::{FilingStatus, TaxConfig},
+    };
+
+    #[test]
+    fn installments_amortize_the_debt_over_the_term() {
+        // 400k at 5% over 30 years
+        assert!((saver().mortgage_installments() - 2147.29).abs() < 0.5);
+    }
+
+    #[test]
+    fn installments_without_interest_split_the_debt_evenly() {
+        let owner = Saver {
+            mortgage_rate: 0.0,
+            ..saver()
+        };
+        assert_eq!(owner.mortgage_installments(), 400000.0 / 360.0);
+    }
+
+    #[test]
+    fn home_appreciates_with_inflation_plus_the_real_rate() {
+        let inflation = [0.024; DEATH];
+        let owner = Saver {
+            inflation_rates: &inflation,
+            home_appreciation_rate: 0.012,
+            ..saver()
+        };
+        assert!((owner.monthly_home_appreciation() - 1500.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn home_prices_follow_the_rate_path_when_it_has_them() {
+        let owner = Saver {
+            inflation_rates: &[0.024; DEATH],
+            home_appreciation_rate: 0.012,
+            home_growth_rates: &[0.06; DEATH],
+            ..saver()
+        };
+        assert!((owner.monthly_home_appreciation() - 2500.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn home_costs_split_value_based_and_fixed_costs() {
+        let mut owner = Saver {
+            monthly_hoa: 200.0,
+            ..saver()
+        };
+        owner.start_pmi();
+        let costs = owner.monthly_home_costs();
+        // 1% property tax and 1% maintenance on 500k, plus the hoa dues and insurance
+        assert!((costs.property_tax - 416.67).abs() < 0.01);
+        assert!((costs.maintenance - 416.67).abs() < 0.01);
+        assert_eq!(costs.hoa, 200.0);
+        assert_eq!(costs.insurance, 150.0);
+        assert_eq!(costs.pmi, 0.0);
+        assert!((owner.monthly_home_expenses() - 1183.33).abs() < 0.01);
+    }
+
+    #[test]
+    fn buying_pays_closing_costs_out_of_savings() {
+        let mut owner = saver();
+        owner.buy_home();
+        // the down payment is already equity, only 3% of the 500k price leaves savings
+        assert_eq!(owner.total_savings, 200000.0 - 15000.0);
+    }
+
+    #[test]
+    fn selling_pays_off_the_mortgage_and_selling_costs() {
+        let mut owner = saver();
+        owner.sell_home();
+        // the 100k of equity was already part of savings, 6% of the 500k price goes to the sale
+        assert_eq!(owner.total_savings, 200000.0 - 30000.0);
+        assert_eq!(owner.liquid_assets(), 170000.0);
+        assert_eq!(owner.home_value, 0.0);
+        assert_eq!(owner.mortgage_debt, 0.0);
+        assert_eq!(owner.cached_mortgage_installment, None);
+        assert_eq!(owner.monthly_rent, owner.rent_after_sale);
+    }
+
+    #[test]
+    fn purchase_takes_the_market_mortgage_rate() {
+        let market = [0.07; DEATH];
+        let mut buyer = Saver {
+            home_value: 0.0,
+            mortgage_debt: 0.0,
+            mortgage_rates: &market,
+            purchase_home_value: 500000.0,
+            down_payment_rate: 0.2,
+            ..saver()
+        };
+        buyer.purchase_home();
+        assert_eq!(buyer.mortgage_rate, 0.07);
+        assert_eq!(buyer.mortgage_debt, 400000.0);
+        assert_eq!(
+            buyer.cached_mortgage_installment,
+            Some(buyer.mortgage_installments())
+        );
+    }
+
+    #[test]
+    fn refinances_when_the_market_rate_drops_far_enough() {
+        let market = [0.04; DEATH];
+        let owner = Saver {
+            mortgage_rates: &market,
+            refinance_rate_drop: Some(0.01),
+            ..saver()
+        };
+        assert!(owner.should_refinance());
+        let small_drop = Saver {
+            refinance_rate_drop: Some(0.015),
+            ..owner.clone()
+        };
+        assert!(!small_drop.should_refinance());
+        let scheduled = Saver {
+            refinance_age: Some(30),
+            ..small_drop.clone()
+        };
+        assert!(scheduled.should_refinance());
+    }
+
+    #[test]
+    fn refinance_reamortizes_at_the_market_rate() {
+        let market = [0.04; DEATH];
+        let mut owner = Saver {
+            mortgage_rates: &market,
+            refinance_term: 15,
+            ..saver()
+        };
+        owner.refinance();
+        // 2% closing costs on the 400k debt
+        assert_eq!(owner.total_savings, 200000.0 - 8000.0);
+        assert_eq!(owner.mortgage_rate, 0.04);
+        // 400k at 4% over 15 years
+        let installment = owner.cached_mortgage_installment.unwrap();
+        assert!((installment - 2958.75).abs() < 0.5);
+    }
+
+    #[test]
+    fn installments_amortize_over_the_term_left() {
+        let owner = Saver {
+            current_age: 45,
+            mortgage_debt: 200000.0,
+            ..saver()
+        };
+        // 200k at 5% over the 15 years left
+        assert_eq!(owner.mortgage_term_months(), 180.0);
+        assert!((owner.mortgage_installments() - 1581.59).abs() < 0.5);
+    }
+
+    fn arm_owner(index: &[f32], mortgage_rate: f32, periodic_cap: f32) -> Saver<'_> {
+        Saver {
+            current_age: 36,
+            mortgage_rate,
+            arm: Some(Arm {
+                fixed_years: 5,
+                margin: 0.025,
+                periodic_cap,
+                lifetime_cap: 0.05,
+                initial_rate: 0.05,
+            }),
+            index_rates: index,
+            ..saver()
+        }
+    }
+
+    #[test]
+    fn arm_resets_after_the_fixed_years() {
+        assert!(arm_owner(&[0.03; DEATH], 0.05, 0.02).should_reset_arm());
+        let fixed = Saver {
+            current_age: 34,
+            ..arm_owner(&[0.03; DEATH], 0.05, 0.02)
+        };
+        assert!(!fixed.should_reset_arm());
+    }
+
+    #[test]
+    fn arm_reset_moves_to_the_index_plus_margin() {
+        let mut owner = arm_owner(&[0.03; DEATH], 0.05, 0.02);
+        owner.reset_arm();
+        assert!((owner.mortgage_rate - 0.055).abs() < 1e-6);
+        assert_eq!(
+            owner.cached_mortgage_installment,
+            Some(owner.mortgage_installments())
+        );
+    }
+
+    #[test]
+    fn arm_reset_is_limited_by_the_periodic_cap() {
+        let mut owner = arm_owner(&[0.10; DEATH], 0.05, 0.02);
+        owner.reset_arm();
+        assert!((owner.mortgage_rate - 0.07).abs() < 1e-6);
+    }
+
+    #[test]
+    fn arm_reset_is_limited_by_the_lifetime_cap() {
+        // the periodic cap would allow 11%, the lifetime cap stops at 5% over the initial rate
+        let mut owner = arm_owner(&[0.10; DEATH], 0.09, 0.02);
+        owner.reset_arm();
+        assert!((owner.mortgage_rate - 0.10).abs() < 1e-6);
+    }
+
+    #[test]
+    fn arm_reset_never_falls_below_the_margin() {
+        let mut owner = arm_owner(&[-0.04; DEATH], 0.03, 0.02);
+        owner.reset_arm();
+        assert!((owner.mortgage_rate - 0.025).abs() < 1e-6);
+    }
+
+    #[test]
+    fn arm_reset_holds_the_rate_with_negative_or_missing_caps() {
+        for cap in [-0.01, f32::NAN] {
+            let mut owner = arm_owner(&[0.10; DEATH], 0.05, cap);
+            owner.reset_arm();
+            assert_eq!(owner.mortgage_rate, 0.05);
+        }
+    }
+
+    #[test]
+    fn prepayment_is_limited_by_the_debt_and_liquid_savings() {
+        let mut owner = saver();
+        owner.prepay_principal(50000.0);
+        assert_eq!(owner.mortgage_debt, 350000.0);
+        // only 50k of the 100k of liquid savings is left after the first prepayment
+        owner.prepay_principal(500000.0);
+        assert_eq!(owner.mortgage_debt, 300000.0);
+        assert_eq!(owner.liquid_assets(), 0.0);
+        owner.prepay_principal(-1000.0);
+        assert_eq!(owner.mortgage_debt, 300000.0);
+    }
+
+    #[test]
+    fn no_pmi_with_twenty_percent_down() {
+        let mut owner = saver();
+        owner.buy_home();
+        assert_eq!(owner.pmi_end_age, Some(30));
+        assert_eq!(owner.monthly_pmi(), 0.0);
+    }
+
+    fn ten_percent_down() -> Saver<'static> {
+        let mut owner = Saver {
+            mortgage_debt: 450000.0,
+            ..saver()
+        };
+        owner.buy_home();
+        owner
+    }
+
+    #[test]
+    fn pmi_is_charged_with_less_than_twenty_percent_down() {
+        let owner = ten_percent_down();
+        assert_eq!(owner.pmi_end_age, None);
+        assert!((owner.monthly_pmi() - 187.5).abs() < 0.01);
+        assert_eq!(owner.monthly_home_costs().pmi, owner.monthly_pmi());
+    }
+
+    #[test]
+    fn pmi_stops_for_good_once_paydown_reaches_the_cutoff() {
+        let mut owner = Saver {
+            current_age: 34,
+            mortgage_debt: 390000.0,
+            ..ten_percent_down()
+        };
+        owner.expenses();
+        assert_eq!(owner.pmi_end_age, Some(34));
+        assert_eq!(owner.monthly_pmi(), 0.0);
+        // a later fall in the home value doesn't bring it back
+        owner.home_value = 400000.0;
+        assert_eq!(owner.monthly_pmi(), 0.0);
+    }
+
+    #[test]
+    fn pmi_stops_once_appreciation_reaches_the_cutoff() {
+        let mut owner = ten_percent_down();
+        owner.expenses();
+        assert_eq!(owner.pmi_end_age, None);
+        owner.home_value = 580000.0;
+        owner.expenses();
+        assert_eq!(owner.pmi_end_age, Some(30));
+        assert_eq!(owner.monthly_pmi(), 0.0);
+    }
+
+    fn retired_seller(home_value: f32) -> Saver<'static> {
+        Saver {
+            home_value,
+            mortgage_debt: 0.0,
+            property_tax_rate: 0.0,
+            home_cost_basis: 515000.0,
+            active_retirement: true,
+            tax: Some(TaxConfig {
+                filing_status: FilingStatus::Single,
+                state_tax_rate: 0.0,
+            }),
+            ..saver()
+        }
+    }
+
+    #[test]
+    fn sale_only_taxes_the_gain_over_basis_and_exclusion() {
+        let mut seller = retired_seller(1000000.0);
+        let savings = seller.total_savings;
+        seller.sell_home();
+        // 940k of proceeds less the 515k basis and 250k exclusion is a 175k gain, 160,400 after the deduction
+        let tax = (160400.0 - 47025.0) * 0.15;
+        assert!((savings - seller.total_savings - 60000.0 - tax).abs() < 1.0);
+    }
+
+    #[test]
+    fn sale_under_the_exclusion_is_tax_free() {
+        let mut seller = retired_seller(700000.0);
+        let savings = seller.total_savings;
+        seller.sell_home();
+        assert_eq!(savings - seller.total_savings, 42000.0);
+    }
+}
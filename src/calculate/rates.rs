@@ -1,43 +1,407 @@
-use super::consts::{Opts, DEATH};
-use rand::{thread_rng, Rng};
+use rand::{rngs::StdRng, thread_rng, Rng, SeedableRng};
 
-pub fn new_rates() -> (Vec<Opts>, Vec<Opts>) {
-    let mut rng = thread_rng();
-    let mut interest: Vec<Opts> = vec![Opts::Float(0.0); DEATH];
-    let mut inflation: Vec<Opts> = vec![Opts::Float(0.0); DEATH];
+use super::{allocation::GlidePath, history::HISTORICAL_RETURNS};
 
-    for rate in inflation.iter_mut().take(DEATH) {
-        *rate = Opts::Float(rng.gen_range(-0.005..0.04) + rng.gen_range(0.0..0.01));
+// one simulated path of annual rates indexed by age
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RatePath {
+    // return on savings, the age band mix of stocks and bonds until a saver's allocation replaces it
+    pub interest: Vec<f32>,
+    pub inflation: Vec<f32>,
+    pub mortgage: Vec<f32>,
+    // short term index adjustable rate mortgages reset against
+    pub index: Vec<f32>,
+    // each year's return standardized by the return model, how good the year was for the market
+    pub market_shock: Vec<f32>,
+    // unit variance noise income shocks mix with the market shock
+    pub wage_noise: Vec<f32>,
+    // nominal rent and home price growth, empty when rent keeps up with inflation and homes with the appreciation rate
+    pub rent_growth: Vec<f32>,
+    pub home_growth: Vec<f32>,
+    // returns of each asset class the allocation mixes into the return on savings
+    pub stocks: Vec<f32>,
+    pub bonds: Vec<f32>,
+    pub cash: Vec<f32>,
+}
+
+// a fresh seed for a new set of rate paths
+pub fn new_seed() -> u64 {
+    thread_rng().gen()
+}
+
+// the same seed always generates the same rate paths
+pub fn seeded_rng(seed: u64) -> StdRng {
+    StdRng::seed_from_u64(seed)
+}
+
+// a standard normal draw (box muller)
+pub fn standard_normal<R: Rng>(rng: &mut R) -> f32 {
+    let u1: f32 = rng.gen_range(f32::EPSILON..1.0);
+    let u2: f32 = rng.gen();
+    (-2.0 * u1.ln()).sqrt() * (2.0 * std::f32::consts::PI * u2).cos()
+}
+
+pub trait ReturnModel {
+    // the market return for each age given that age's inflation
+    fn returns<R: Rng>(&self, rng: &mut R, inflation: &[f32]) -> Vec<f32>;
+    // expected return and standard deviation given the year's inflation, how far a return sits from it is the market shock
+    fn moments(&self, inflation: f32) -> (f32, f32);
+}
+
+// uniform stock returns from -7.5% to 20%, half of inflation is passed through
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Uniform;
+
+impl Uniform {
+    fn range(inflation: f32) -> (f32, f32) {
+        (-0.075 + inflation / 2.0, 0.20 + inflation / 2.0)
+    }
+}
+
+impl ReturnModel for Uniform {
+    fn returns<R: Rng>(&self, rng: &mut R, inflation: &[f32]) -> Vec<f32> {
+        inflation
+            .iter()
+            .map(|infl| {
+                let (low, high) = Uniform::range(*infl);
+                rng.gen_range(low..high)
+            })
+            .collect()
     }
 
-    for (idx, infl) in inflation.iter().enumerate() {
-        match idx {
-            0..=35 => {
-                interest[idx] = Opts::Float(rng.gen_range(
-                    (-0.075 + infl.get_float_ref() / 2.0)..(0.20 + infl.get_float_ref() / 2.0),
-                ));
-            }
-            36..=49 => {
-                interest[idx] = Opts::Float(rng.gen_range(
-                    (-0.05 + infl.get_float_ref() / 2.0)..(0.175 + infl.get_float_ref() / 2.0),
-                ));
-            }
-            50..=64 => {
-                interest[idx] = Opts::Float(rng.gen_range(
-                    (-0.035 + infl.get_float_ref() / 2.0)..(0.15 + infl.get_float_ref() / 2.0),
-                ));
-            }
-            65..=80 => {
-                interest[idx] = Opts::Float(rng.gen_range(
-                    (-0.02 + infl.get_float_ref() / 2.0)..(0.125 + infl.get_float_ref() / 2.0),
-                ));
-            }
-            _ => {
-                interest[idx] = Opts::Float(rng.gen_range(
-                    (-0.005 + infl.get_float_ref() / 2.0)..(0.1 + infl.get_float_ref() / 2.0),
-                ));
+    fn moments(&self, inflation: f32) -> (f32, f32) {
+        let (low, high) = Uniform::range(inflation);
+        ((low + high) / 2.0, (high - low) / 12f32.sqrt())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Normal {
+    pub mean: f32,
+    pub std_dev: f32,
+}
+
+impl ReturnModel for Normal {
+    fn returns<R: Rng>(&self, rng: &mut R, inflation: &[f32]) -> Vec<f32> {
+        inflation
+            .iter()
+            .map(|_| self.mean + self.std_dev * standard_normal(rng))
+            .collect()
+    }
+
+    fn moments(&self, _: f32) -> (f32, f32) {
+        (self.mean, self.std_dev)
+    }
+}
+
+// growth (one plus the return) is lognormal with the given arithmetic mean and standard deviation,
+// returns are skewed and never lose more than everything
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Lognormal {
+    pub mean: f32,
+    pub std_dev: f32,
+}
+
+impl ReturnModel for Lognormal {
+    fn returns<R: Rng>(&self, rng: &mut R, inflation: &[f32]) -> Vec<f32> {
+        let growth = (1.0 + self.mean).max(f32::EPSILON);
+        let variance = (1.0 + (self.std_dev / growth).powi(2)).ln();
+        let mu = growth.ln() - variance / 2.0;
+        inflation
+            .iter()
+            .map(|_| (mu + variance.sqrt() * standard_normal(rng)).exp() - 1.0)
+            .collect()
+    }
+
+    fn moments(&self, _: f32) -> (f32, f32) {
+        (self.mean, self.std_dev)
+    }
+}
+
+// student's t scaled to the standard deviation, fewer degrees of freedom give fatter tails
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StudentT {
+    pub mean: f32,
+    pub std_dev: f32,
+    pub degrees_of_freedom: u8,
+}
+
+impl ReturnModel for StudentT {
+    fn returns<R: Rng>(&self, rng: &mut R, inflation: &[f32]) -> Vec<f32> {
+        // the variance is only finite past 2 degrees of freedom
+        let dof = self.degrees_of_freedom.max(3);
+        let scale = ((dof as f32 - 2.0) / dof as f32).sqrt();
+        inflation
+            .iter()
+            .map(|_| {
+                let chi_squared: f32 = (0..dof).map(|_| standard_normal(rng).powi(2)).sum();
+                let t = standard_normal(rng) / (chi_squared / dof as f32).sqrt();
+                self.mean + self.std_dev * scale * t
+            })
+            .collect()
+    }
+
+    fn moments(&self, _: f32) -> (f32, f32) {
+        (self.mean, self.std_dev)
+    }
+}
+
+// runs of consecutive historical stock returns starting from random years, keeps history's fat tails and streaks
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BlockBootstrap {
+    pub block_years: u8,
+}
+
+impl ReturnModel for BlockBootstrap {
+    fn returns<R: Rng>(&self, rng: &mut R, inflation: &[f32]) -> Vec<f32> {
+        let block_years = self.block_years.max(1) as usize;
+        let mut year = 0;
+        (0..inflation.len())
+            .map(|age| {
+                if age % block_years == 0 {
+                    year = rng.gen_range(0..HISTORICAL_RETURNS.len());
+                } else {
+                    year = (year + 1) % HISTORICAL_RETURNS.len();
+                }
+                HISTORICAL_RETURNS[year].0
+            })
+            .collect()
+    }
+
+    fn moments(&self, _: f32) -> (f32, f32) {
+        let returns = HISTORICAL_RETURNS.iter().map(|(stocks, ..)| stocks);
+        let count = HISTORICAL_RETURNS.len() as f32;
+        let mean = returns.clone().sum::<f32>() / count;
+        let variance = returns.map(|r| (r - mean).powi(2)).sum::<f32>() / count;
+        (mean, variance.sqrt())
+    }
+}
+
+// normal returns from a bull or a bear market, each year the market can switch with the switch chance
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RegimeSwitching {
+    pub bull_mean: f32,
+    pub bull_std_dev: f32,
+    pub bear_mean: f32,
+    pub bear_std_dev: f32,
+    pub bull_to_bear: f32,
+    pub bear_to_bull: f32,
+}
+
+impl RegimeSwitching {
+    // long run share of years spent in a bear market
+    fn bear_share(&self) -> f32 {
+        let switches = self.bull_to_bear + self.bear_to_bull;
+        if switches > 0.0 {
+            self.bull_to_bear / switches
+        } else {
+            0.0
+        }
+    }
+}
+
+impl ReturnModel for RegimeSwitching {
+    fn returns<R: Rng>(&self, rng: &mut R, inflation: &[f32]) -> Vec<f32> {
+        let mut bear = rng.gen::<f32>() < self.bear_share();
+        inflation
+            .iter()
+            .map(|_| {
+                let switch = if bear {
+                    self.bear_to_bull
+                } else {
+                    self.bull_to_bear
+                };
+                if rng.gen::<f32>() < switch {
+                    bear = !bear;
+                }
+                let (mean, std_dev) = if bear {
+                    (self.bear_mean, self.bear_std_dev)
+                } else {
+                    (self.bull_mean, self.bull_std_dev)
+                };
+                mean + std_dev * standard_normal(rng)
+            })
+            .collect()
+    }
+
+    fn moments(&self, _: f32) -> (f32, f32) {
+        let bear = self.bear_share();
+        let mean = (1.0 - bear) * self.bull_mean + bear * self.bear_mean;
+        let second_moment = (1.0 - bear) * (self.bull_std_dev.powi(2) + self.bull_mean.powi(2))
+            + bear * (self.bear_std_dev.powi(2) + self.bear_mean.powi(2));
+        (mean, (second_moment - mean.powi(2)).max(0.0).sqrt())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Model {
+    Uniform(Uniform),
+    Normal(Normal),
+    Lognormal(Lognormal),
+    StudentT(StudentT),
+    BlockBootstrap(BlockBootstrap),
+    RegimeSwitching(RegimeSwitching),
+}
+
+impl Default for Model {
+    fn default() -> Self {
+        Model::Uniform(Uniform)
+    }
+}
+
+impl ReturnModel for Model {
+    fn returns<R: Rng>(&self, rng: &mut R, inflation: &[f32]) -> Vec<f32> {
+        match self {
+            Model::Uniform(model) => model.returns(rng, inflation),
+            Model::Normal(model) => model.returns(rng, inflation),
+            Model::Lognormal(model) => model.returns(rng, inflation),
+            Model::StudentT(model) => model.returns(rng, inflation),
+            Model::BlockBootstrap(model) => model.returns(rng, inflation),
+            Model::RegimeSwitching(model) => model.returns(rng, inflation),
+        }
+    }
+
+    fn moments(&self, inflation: f32) -> (f32, f32) {
+        match self {
+            Model::Uniform(model) => model.moments(inflation),
+            Model::Normal(model) => model.moments(inflation),
+            Model::Lognormal(model) => model.moments(inflation),
+            Model::StudentT(model) => model.moments(inflation),
+            Model::BlockBootstrap(model) => model.moments(inflation),
+            Model::RegimeSwitching(model) => model.moments(inflation),
+        }
+    }
+}
+
+// rates for each age up to the given number of years, a household runs past the death age while a younger partner lives
+pub fn new_rates<R: Rng>(rng: &mut R, years: usize, model: &Model) -> RatePath {
+    let mut inflation: Vec<f32> = vec![0.0; years];
+
+    for rate in inflation.iter_mut().take(years) {
+        *rate = rng.gen_range(-0.005..0.04) + rng.gen_range(0.0..0.01);
+    }
+
+    // the return model draws stock returns, nothing loses more than everything
+    let stocks: Vec<f32> = model
+        .returns(rng, &inflation)
+        .into_iter()
+        .map(|rate| rate.max(-1.0))
+        .collect();
+    let market_shock: Vec<f32> = stocks
+        .iter()
+        .zip(&inflation)
+        .map(|(rate, infl)| {
+            let (mean, std_dev) = model.moments(*infl);
+            if std_dev > 0.0 {
+                (rate - mean) / std_dev
+            } else {
+                0.0
             }
+        })
+        .collect();
+
+    let wage_noise: Vec<f32> = (0..years)
+        .map(|_| rng.gen_range(-3f32.sqrt()..3f32.sqrt()))
+        .collect();
+
+    // market mortgage rates, a persistent real rate around 3% on top of inflation
+    let mut mortgage: Vec<f32> = vec![0.0; years];
+    let mut real_rate = 0.03;
+    for (idx, infl) in inflation.iter().enumerate() {
+        real_rate = 0.8 * real_rate + 0.2 * 0.03 + rng.gen_range(-0.01..0.01);
+        mortgage[idx] = (real_rate + infl).max(0.02);
+    }
+
+    // short term rates, a short real rate around 0.5% on top of inflation
+    let mut short_rates: Vec<f32> = vec![0.0; years];
+    let mut short_rate = 0.005;
+    for (idx, infl) in inflation.iter().enumerate() {
+        short_rate = 0.7 * short_rate + 0.3 * 0.005 + rng.gen_range(-0.015..0.015);
+        short_rates[idx] = (short_rate + infl).max(0.0);
+    }
+
+    // bonds pay a real 2% with their own noise
+    let bonds: Vec<f32> = inflation
+        .iter()
+        .map(|infl| (infl + 0.02 + 0.06 * standard_normal(rng)).max(-1.0))
+        .collect();
+
+    let mut rates = RatePath {
+        interest: vec![],
+        inflation,
+        mortgage,
+        // adjustable rate mortgages reset against the short rate
+        index: short_rates.clone(),
+        market_shock,
+        wage_noise,
+        rent_growth: vec![],
+        home_growth: vec![],
+        stocks,
+        bonds,
+        // cash is held in bills that earn the short rate
+        cash: short_rates,
+    };
+    rates.interest = GlidePath::AgeBands.portfolio_returns(&rates, 0, 0, true);
+    rates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DRAWS: usize = 20000;
+
+    fn mean_and_std_dev(returns: &[f32]) -> (f32, f32) {
+        let count = returns.len() as f32;
+        let mean = returns.iter().sum::<f32>() / count;
+        let variance = returns.iter().map(|r| (r - mean).powi(2)).sum::<f32>() / count;
+        (mean, variance.sqrt())
+    }
+
+    #[test]
+    fn normal_returns_match_the_mean_and_spread() {
+        let model = Normal {
+            mean: 0.07,
+            std_dev: 0.15,
+        };
+        let returns = model.returns(&mut seeded_rng(1), &[0.0; DRAWS]);
+        let (mean, std_dev) = mean_and_std_dev(&returns);
+        assert!((mean - 0.07).abs() < 0.005, "mean {}", mean);
+        assert!((std_dev - 0.15).abs() < 0.005, "std dev {}", std_dev);
+    }
+
+    #[test]
+    fn lognormal_returns_match_the_mean_and_spread_without_losing_everything() {
+        let model = Lognormal {
+            mean: 0.07,
+            std_dev: 0.15,
+        };
+        let returns = model.returns(&mut seeded_rng(1), &[0.0; DRAWS]);
+        let (mean, std_dev) = mean_and_std_dev(&returns);
+        assert!((mean - 0.07).abs() < 0.005, "mean {}", mean);
+        assert!((std_dev - 0.15).abs() < 0.005, "std dev {}", std_dev);
+        assert!(returns.iter().all(|r| *r > -1.0));
+    }
+
+    #[test]
+    fn block_bootstrap_only_draws_historical_years_in_order() {
+        let model = BlockBootstrap { block_years: 5 };
+        let returns = model.returns(&mut seeded_rng(1), &[0.0; 100]);
+        // every year of history that could have produced each return
+        let years: Vec<Vec<usize>> = returns
+            .iter()
+            .map(|r| {
+                (0..HISTORICAL_RETURNS.len())
+                    .filter(|year| HISTORICAL_RETURNS[*year].0 == *r)
+                    .collect()
+            })
+            .collect();
+        assert!(years.iter().all(|candidates| !candidates.is_empty()));
+        // years follow each other within a block
+        for age in (0..100).filter(|age| age % 5 != 0) {
+            assert!(years[age - 1]
+                .iter()
+                .any(|year| years[age].contains(&((year + 1) % HISTORICAL_RETURNS.len()))));
         }
     }
-    (interest, inflation)
 }
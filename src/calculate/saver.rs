@@ -1,60 +1,173 @@
-use leptos::{ReadSignal, SignalWithUntracked};
 //use serde::{Deserialize, Serialize};
 use super::{
-    consts::Opts,
+    accounts::{Account, Accounts},
+    allocation::GlidePath,
+    benefits::Benefits,
+    events::{LifeEvent, LifeEvents},
+    household::{Household, Partner},
     owner::{self, Owner},
+    rates::RatePath,
+    spending::RetirementSpending,
+    tax::{TaxConfig, Taxpayer},
+    wages::{WageGrowth, Wages},
+    withdrawal::{Strategy, WithdrawalError, WithdrawalStrategy},
 };
-pub const STD_MONTHLY_WITHDRAWAL_RATE: f32 = 0.04 / 12.0;
 
 #[derive(Clone)]
 pub enum SaverType {
     HomeOwner,
     Renter,
+    // rents until the purchase age then buys a home
+    BuyLater,
 }
 
 // #[derive(Debug, Clone, Serialize, Deserialize)]
 // #[serde(rename_all = "camelCase")]
 
 #[derive(Clone)]
-pub struct Saver {
+pub struct Saver<'a> {
     pub current_age: u8,
     pub retirement_age: u8,
     pub total_savings: f32,
     pub monthly_income: f32,
     pub monthly_expenses: f32,
     pub home_value: f32,
+    pub home_appreciation_rate: f32,
     pub monthly_rent: f32,
     pub mortgage_debt: f32,
     pub mortgage_rate: f32,
     pub mortgage_term: u8,
+    pub mortgage_start_age: u8,
+    pub arm: Option<owner::Arm>,
     pub min_baseline_retirement_income: f32,
     pub max_baseline_retirement_income: f32,
-    pub interest_rates: ReadSignal<Vec<Opts>>,
-    pub inflation_rates: ReadSignal<Vec<Opts>>,
+    pub interest_rates: &'a [f32],
+    pub inflation_rates: &'a [f32],
+    pub mortgage_rates: &'a [f32],
+    pub index_rates: &'a [f32],
+    pub market_shocks: &'a [f32],
+    pub wage_noise: &'a [f32],
+    pub rent_growth_rates: &'a [f32],
+    pub home_growth_rates: &'a [f32],
     pub home_savings: Vec<f32>,
     pub rental_savings: Vec<f32>,
+    pub buy_later_savings: Vec<f32>,
+    pub mortgage_payments: Vec<f32>,
     pub active_retirement: bool,
     pub home_owned_age: Option<u8>,
     pub cached_mortgage_installment: Option<f32>,
-    pub home_expenses: f32,
+    pub property_tax_rate: f32,
+    pub maintenance_rate: f32,
+    pub monthly_hoa: f32,
+    pub monthly_insurance: f32,
+    pub closing_cost_rate: f32,
+    pub selling_cost_rate: f32,
+    pub sale_age: Option<u8>,
+    pub rent_after_sale: f32,
+    pub purchase_age: Option<u8>,
+    pub purchase_home_value: f32,
+    pub down_payment_rate: f32,
+    pub refinance_age: Option<u8>,
+    pub refinance_rate_drop: Option<f32>,
+    pub refinance_term: u8,
+    pub refinance_cost_rate: f32,
+    pub extra_monthly_principal: f32,
+    pub extra_annual_principal: f32,
+    pub mortgage_interest_paid: f32,
+    pub pmi_rate: f32,
+    pub pmi_ltv_cutoff: f32,
+    // the age pmi stopped, the purchase age itself when the loan started with 20% or more down
+    pub pmi_end_age: Option<u8>,
+    // None treats monthly income as take home pay
+    pub tax: Option<TaxConfig>,
+    // prices relative to the current age, scales the tax brackets with inflation
+    pub price_level: f32,
+    // what was paid into the taxable account, the rest of it is unrealized gains
+    pub cost_basis: f32,
+    // purchase price plus closing costs
+    pub home_cost_basis: f32,
+    // primary residence gain excluded from tax when the home is sold
+    pub home_sale_exclusion: f32,
+    // retirement account balances, part of the liquid savings
+    pub pre_tax_savings: f32,
+    pub roth_savings: f32,
+    // percents of monthly income
+    pub pre_tax_contribution_rate: f32,
+    pub roth_contribution_rate: f32,
+    pub employer_match_rate: f32,
+    pub rmd_age: u8,
+    // accounts are drawn down in this order when spending is more than income
+    pub withdrawal_order: [Account; 3],
+    pub withdrawal_strategy: Strategy,
+    // this year's planned spending out of savings in retirement, grows with inflation through the year
+    pub annual_withdrawal: Option<f32>,
+    // set when the strategy fails, the run stops at that age
+    pub withdrawal_error: Option<WithdrawalError>,
+    // monthly benefits grown with inflation, paid from their starting ages
+    pub social_security_age: u8,
+    pub social_security_benefit: f32,
+    pub pension_age: u8,
+    pub pension_benefit: f32,
+    pub pension_cola: bool,
+    // living expenses in retirement as a percent of those before
+    pub retirement_expense_rate: f32,
+    // monthly out of pocket healthcare in retirement, grows faster than inflation by the healthcare inflation
+    pub healthcare_cost: f32,
+    pub healthcare_inflation: f32,
+    // real living expenses follow the retirement spending smile
+    pub spending_smile: bool,
+    pub life_events: Vec<LifeEvent>,
+    // real wage growth on top of inflation, with yearly shocks that can move with the market
+    pub wage_growth: WageGrowth,
+    pub wage_volatility: f32,
+    pub wage_market_correlation: f32,
+    // None for a single person household
+    pub partner: Option<Partner>,
+    // savings earn this mix of stocks, bonds and cash by age, the simulation builds the interest rates from it
+    pub allocation: GlidePath,
+    pub annual_rebalancing: bool,
 }
 
-impl Saver {
-    // monthly inflation rate
-    pub fn monthly_inflation(&self) -> impl Fn() -> f32 + '_ {
-        move || {
-            self.inflation_rates.with_untracked(|inflation_rates| {
-                inflation_rates[self.current_age as usize].get_float_ref() / 12.0
-            })
+impl<'a> Saver<'a> {
+    // copy of this saver that runs on the given rate path
+    pub fn with_rates<'b>(&self, rates: &'b RatePath) -> Saver<'b>
+    where
+        'a: 'b,
+    {
+        Saver {
+            interest_rates: &rates.interest,
+            inflation_rates: &rates.inflation,
+            mortgage_rates: &rates.mortgage,
+            index_rates: &rates.index,
+            market_shocks: &rates.market_shock,
+            wage_noise: &rates.wage_noise,
+            rent_growth_rates: &rates.rent_growth,
+            home_growth_rates: &rates.home_growth,
+            ..self.clone()
         }
     }
+    // monthly inflation rate
+    pub fn monthly_inflation(&self) -> f32 {
+        self.inflation_rates[self.current_age as usize] / 12.0
+    }
+    // monthly rent growth, with inflation unless the rate path moves rents on its own
+    pub fn monthly_rent_growth(&self) -> f32 {
+        self.rent_growth_rates
+            .get(self.current_age as usize)
+            .map_or(self.monthly_inflation(), |rate| rate / 12.0)
+    }
+    // monthly home price growth, inflation plus the real appreciation rate unless the rate path moves prices on its own
+    pub fn monthly_home_growth(&self) -> f32 {
+        self.home_growth_rates
+            .get(self.current_age as usize)
+            .map_or(
+                self.monthly_inflation() + self.home_appreciation_rate / 12.0,
+                |rate| rate / 12.0,
+            )
+    }
     // monthly interest rate
-    pub fn monthly_interest(&self) -> impl Fn() -> f32 + '_ {
-        move || {
-            self.interest_rates.with_untracked(|interest_rates| {
-                interest_rates[self.current_age as usize].get_float_ref() / 12.0
-            })
-        }
+    pub fn monthly_interest(&self) -> f32 {
+        self.interest_rates[self.current_age as usize] / 12.0
     }
 
     // calculate liquid assets (total savings - (home value - mortgage debt))
@@ -66,71 +179,95 @@ impl Saver {
         }
     }
 
-    // calculate monthly withdrawal rate (4% of total savings or min/max baseline retirement income)
-    pub fn monthly_withdrawal(&self) -> f32 {
+    // this year's withdrawal from the strategy plus benefits held between the min and max baseline retirement income
+    // (we always need more than the min and never more than the max), without savings only benefits can be spent,
+    // but living expenses, healthcare and housing are always paid
+    pub fn retirement_spending(&self) -> f32 {
+        let benefits = Benefits::monthly_benefits(self);
+        let spending = (self.annual_withdrawal.unwrap_or(0.0) / 12.0 + benefits)
+            .min(self.max_baseline_retirement_income)
+            .max(self.min_baseline_retirement_income);
         if self.liquid_assets() <= 0.0 {
-            0.0
-        } else if self.min_baseline_retirement_income
-            <= self.liquid_assets() * STD_MONTHLY_WITHDRAWAL_RATE
-            && self.max_baseline_retirement_income
-                >= self.liquid_assets() * STD_MONTHLY_WITHDRAWAL_RATE
-        {
-            -1.0 * STD_MONTHLY_WITHDRAWAL_RATE * self.liquid_assets()
-        // if the min baseline retirement income is greater than the standard monthly withdrawal rate use that (we always need more than the min)
-        } else if self.min_baseline_retirement_income
-            > self.liquid_assets() * STD_MONTHLY_WITHDRAWAL_RATE
-        {
-            -1.0 * (self.min_baseline_retirement_income)
-        // if the max baseline retirement income is less than the standard monthly withdrawal rate use that (we never need more than the max)
-        } else if self.max_baseline_retirement_income
-            < self.liquid_assets() * STD_MONTHLY_WITHDRAWAL_RATE
-        {
-            -1.0 * (self.max_baseline_retirement_income)
+            spending.min(benefits)
         } else {
-            unreachable!("shouldn't be here")
+            spending
         }
+        .max(RetirementSpending::monthly_needs(self))
     }
     // calculate monthly interest earnings
     pub fn interest_earnings(&self) -> f32 {
-        self.liquid_assets() * self.monthly_interest()()
+        self.liquid_assets() * self.monthly_interest()
     }
-    // income is monthly income + interest earnings
+    // income is monthly wages and benefits after taxes plus the employer match, in retirement benefits less
+    // spending beyond what expenses already charge, so withdrawals only cover the gap (benefits beyond spending are saved)
     pub fn income(&mut self) -> f32 {
         if self.active_retirement {
-            self.monthly_expenses = 0.0;
-            self.monthly_withdrawal()
+            Benefits::monthly_benefits(self)
+                - (self.retirement_spending() - RetirementSpending::monthly_needs(self))
+                - Taxpayer::monthly_taxes(self)
         } else {
-            self.monthly_income
+            LifeEvents::monthly_wages(self) + Benefits::monthly_benefits(self)
+                - Taxpayer::monthly_taxes(self)
+                + Accounts::monthly_employer_match(self)
         }
     }
     // end of month expenses for a renter and owner (if renter -> owner is zeroed out, if owner -> renter is zeroed out)
     pub fn expenses(&mut self) -> f32 {
-        self.monthly_expenses + owner::Owner::expenses(self) + self.monthly_rent
+        self.monthly_expenses
+            + LifeEvents::monthly_event_expenses(self)
+            + RetirementSpending::monthly_healthcare(self)
+            + owner::Owner::expenses(self)
+            + self.monthly_rent
     }
-    // c
+    // apply the month's cash flow, then grow prices and the home value (equity is part of total savings)
     pub fn apply_monthly_changes(&mut self) -> f32 {
-        let monthly_inflation = self.monthly_inflation()();
+        let monthly_inflation = self.monthly_inflation();
+        // taxes are figured before expenses pay down the mortgage
         let month_end = self.total_savings + self.income() - self.expenses();
-        self.monthly_income *= 1.0 + monthly_inflation;
+        let appreciation = Owner::monthly_home_appreciation(self);
+        self.home_value += appreciation;
+        self.monthly_income *= 1.0 + monthly_inflation + Wages::real_wage_growth(self) / 12.0;
+        Household::grow_partner_income(self, monthly_inflation);
         self.monthly_expenses *= 1.0 + monthly_inflation;
-        self.monthly_rent *= 1.0 + monthly_inflation;
-        self.home_expenses *= 1.0 + monthly_inflation;
+        self.monthly_rent *= 1.0 + self.monthly_rent_growth();
+        self.rent_after_sale *= 1.0 + self.monthly_rent_growth();
+        self.purchase_home_value *= 1.0 + self.monthly_home_growth();
+        self.monthly_hoa *= 1.0 + monthly_inflation;
+        self.monthly_insurance *= 1.0 + monthly_inflation;
         self.min_baseline_retirement_income *= 1.0 + monthly_inflation;
         self.max_baseline_retirement_income *= 1.0 + monthly_inflation;
-        month_end
+        self.price_level *= 1.0 + monthly_inflation;
+        Benefits::inflate_benefits(self, monthly_inflation);
+        RetirementSpending::inflate_healthcare(self, monthly_inflation);
+        if let Some(withdrawal) = self.annual_withdrawal.as_mut() {
+            *withdrawal *= 1.0 + monthly_inflation;
+        }
+        month_end + appreciation
     }
     // run through months then apply the total savings to show only the end of year savings
     pub fn apply_annual_changes(&mut self, st: &SaverType) {
-        for _ in 0..12 {
+        for month in 0..12 {
             // apply interest on the savings from the month prior
             let interest = self.interest_earnings();
+            let taxable = Accounts::taxable_savings(self);
+            let pre_tax = Accounts::monthly_pre_tax_contribution(self)
+                + Accounts::monthly_employer_match(self);
+            let roth = Accounts::monthly_roth_contribution(self);
+            if month == 0 {
+                LifeEvents::receive_windfalls(self);
+            }
             match self.apply_monthly_changes() {
                 // you can not spend continue if you have no more than your home
                 num if num > self.home_value - self.mortgage_debt => {
                     if self.total_savings > 1000000000.0 {
                         self.total_savings = 1000000000.0;
                     } else {
-                        self.total_savings = num + interest;
+                        // interest is growth, not basis
+                        self.total_savings = num;
+                        Accounts::contribute(self, pre_tax, roth);
+                        Accounts::settle_taxable_change(self, taxable, 12.0);
+                        self.total_savings += interest;
+                        Accounts::grow_accounts(self);
                     }
                 }
                 _ => {
@@ -146,14 +283,26 @@ impl Saver {
             SaverType::Renter => {
                 self.rental_savings[self.current_age as usize] = self.total_savings;
             }
+            SaverType::BuyLater => {
+                self.buy_later_savings[self.current_age as usize] = self.total_savings;
+            }
         }
     }
 
     // end of month income adjusted for inflation
     pub fn calculate_savings(&mut self, st: SaverType, death_age: u8) -> Vec<f32> {
+        self.annual_withdrawal = None;
+        self.withdrawal_error = None;
         match st {
             SaverType::HomeOwner => {
+                self.mortgage_payments.fill(0.0);
+                self.mortgage_interest_paid = 0.0;
+                self.mortgage_start_age = self.current_age;
                 self.cached_mortgage_installment = Some(Owner::mortgage_installments(self));
+                self.mortgage_payments[self.current_age as usize] =
+                    self.cached_mortgage_installment.unwrap_or(0.0);
+                Owner::own_home(self);
+                Accounts::open_accounts(self);
                 self.home_savings.fill(0.0);
                 if self.mortgage_debt == 0.0 {
                     self.home_owned_age = Some(self.current_age);
@@ -163,15 +312,79 @@ impl Saver {
                 self.home_savings[self.current_age as usize] = self.total_savings;
             }
             SaverType::Renter => {
+                Accounts::open_accounts(self);
                 self.rental_savings.fill(0.0);
                 self.rental_savings[self.current_age as usize] = self.total_savings;
             }
+            SaverType::BuyLater => {
+                self.mortgage_payments.fill(0.0);
+                self.mortgage_interest_paid = 0.0;
+                Accounts::open_accounts(self);
+                self.buy_later_savings.fill(0.0);
+                self.buy_later_savings[self.current_age as usize] = self.total_savings;
+            }
+        }
+        let last_age = Household::last_age(self, death_age);
+        for savings in [
+            &mut self.home_savings,
+            &mut self.rental_savings,
+            &mut self.buy_later_savings,
+            &mut self.mortgage_payments,
+        ] {
+            savings.resize(last_age as usize, 0.0);
         }
         self.current_age += 1;
-        while self.current_age < death_age && self.total_savings > 0.0 {
-            self.active_retirement = self.current_age >= self.retirement_age;
+        Household::age_partner(self);
+        while self.current_age < last_age && self.total_savings > 0.0 {
+            let retiring = !self.active_retirement && Household::is_retired(self);
+            self.active_retirement = Household::is_retired(self);
+            if retiring {
+                RetirementSpending::retire(self);
+            } else if self.active_retirement {
+                RetirementSpending::apply_spending_smile(self);
+            }
+            if self.active_retirement {
+                match self.withdrawal_strategy.annual_withdrawal(self) {
+                    Ok(withdrawal) => self.annual_withdrawal = Some(withdrawal),
+                    Err(error) => {
+                        self.withdrawal_error = Some(error);
+                        break;
+                    }
+                }
+            }
+            let taxable = Accounts::taxable_savings(self);
+            if let SaverType::BuyLater = st {
+                if self.purchase_age == Some(self.current_age) {
+                    if Owner::can_afford_purchase(self) {
+                        Owner::purchase_home(self);
+                    } else {
+                        // wait a year for savings to catch up with the down payment and closing costs
+                        self.purchase_age = self.current_age.checked_add(1);
+                    }
+                }
+            }
+            if let SaverType::HomeOwner | SaverType::BuyLater = st {
+                if self.sale_age == Some(self.current_age) && self.home_value > 0.0 {
+                    Owner::sell_home(self);
+                }
+                Owner::prepay_principal(self, self.extra_annual_principal);
+                if Owner::should_refinance(self) {
+                    Owner::refinance(self);
+                } else if Owner::should_reset_arm(self) {
+                    Owner::reset_arm(self);
+                }
+                self.mortgage_payments[self.current_age as usize] = if self.mortgage_debt > 0.0 {
+                    self.cached_mortgage_installment.unwrap_or(0.0)
+                } else {
+                    0.0
+                };
+            }
+            // purchases, sales, prepayments and refinance costs move money in and out of liquid savings
+            Accounts::settle_taxable_change(self, taxable, 1.0);
+            Accounts::take_required_distribution(self);
             self.apply_annual_changes(&st);
             self.current_age += 1;
+            Household::age_partner(self);
         }
 
         match st {
@@ -180,6 +393,186 @@ impl Saver {
                 self.home_savings.clone()
             }
             SaverType::Renter => self.rental_savings.clone(),
+            SaverType::BuyLater => {
+                self.cached_mortgage_installment = None;
+                self.buy_later_savings.clone()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::calculate::{
+        accounts::WITHDRAWAL_ORDERS, consts::DEATH, withdrawal::ConstantDollar,
+    };
+
+    // a single 30 year old owner with a 400k fixed rate mortgage on a 500k home and flat zero rate paths,
+    // no taxes, benefits or contributions unless a test sets them
+    pub fn saver() -> Saver<'static> {
+        Saver {
+            current_age: 30,
+            retirement_age: 65,
+            total_savings: 200000.0,
+            monthly_income: 14000.0,
+            monthly_expenses: 5000.0,
+            home_value: 500000.0,
+            home_appreciation_rate: 0.0,
+            monthly_rent: 0.0,
+            mortgage_debt: 400000.0,
+            mortgage_rate: 0.05,
+            mortgage_term: 30,
+            mortgage_start_age: 30,
+            arm: None,
+            min_baseline_retirement_income: 2000.0,
+            max_baseline_retirement_income: 3000.0,
+            interest_rates: &[0.0; DEATH],
+            inflation_rates: &[0.0; DEATH],
+            mortgage_rates: &[0.0; DEATH],
+            index_rates: &[0.0; DEATH],
+            market_shocks: &[0.0; DEATH],
+            wage_noise: &[0.0; DEATH],
+            rent_growth_rates: &[],
+            home_growth_rates: &[],
+            home_savings: vec![0.0; DEATH],
+            rental_savings: vec![0.0; DEATH],
+            buy_later_savings: vec![0.0; DEATH],
+            mortgage_payments: vec![0.0; DEATH],
+            active_retirement: false,
+            home_owned_age: None,
+            cached_mortgage_installment: None,
+            property_tax_rate: 0.01,
+            maintenance_rate: 0.01,
+            monthly_hoa: 0.0,
+            monthly_insurance: 150.0,
+            closing_cost_rate: 0.03,
+            selling_cost_rate: 0.06,
+            sale_age: None,
+            rent_after_sale: 2000.0,
+            purchase_age: None,
+            purchase_home_value: 0.0,
+            down_payment_rate: 0.0,
+            refinance_age: None,
+            refinance_rate_drop: None,
+            refinance_term: 30,
+            refinance_cost_rate: 0.02,
+            extra_monthly_principal: 0.0,
+            extra_annual_principal: 0.0,
+            mortgage_interest_paid: 0.0,
+            pmi_rate: 0.005,
+            pmi_ltv_cutoff: 0.78,
+            pmi_end_age: None,
+            tax: None,
+            price_level: 1.0,
+            cost_basis: 0.0,
+            home_cost_basis: 0.0,
+            home_sale_exclusion: 250000.0,
+            pre_tax_savings: 0.0,
+            roth_savings: 0.0,
+            pre_tax_contribution_rate: 0.0,
+            roth_contribution_rate: 0.0,
+            employer_match_rate: 0.0,
+            rmd_age: 73,
+            withdrawal_order: WITHDRAWAL_ORDERS[0],
+            withdrawal_strategy: Strategy::ConstantDollar(ConstantDollar { rate: 0.04 }),
+            annual_withdrawal: None,
+            withdrawal_error: None,
+            social_security_age: 67,
+            social_security_benefit: 0.0,
+            pension_age: 65,
+            pension_benefit: 0.0,
+            pension_cola: false,
+            retirement_expense_rate: 1.0,
+            healthcare_cost: 0.0,
+            healthcare_inflation: 0.0,
+            spending_smile: false,
+            life_events: vec![],
+            wage_growth: WageGrowth::Flat,
+            wage_volatility: 0.0,
+            wage_market_correlation: 0.0,
+            partner: None,
+            allocation: GlidePath::default(),
+            annual_rebalancing: true,
         }
     }
+
+    #[test]
+    fn renter_saves_income_left_after_expenses_and_rent() {
+        let mut renter = Saver {
+            home_value: 0.0,
+            mortgage_debt: 0.0,
+            monthly_rent: 2000.0,
+            monthly_insurance: 0.0,
+            ..saver()
+        };
+        let savings = renter.calculate_savings(SaverType::Renter, DEATH as u8);
+        assert_eq!(savings[30], 200000.0);
+        assert_eq!(savings[31], 200000.0 + 12.0 * 7000.0);
+        assert_eq!(savings[32], 200000.0 + 24.0 * 7000.0);
+    }
+
+    #[test]
+    fn owner_keeps_the_home_without_a_sale_age() {
+        let mut owner = Saver { ..saver() };
+        owner.calculate_savings(SaverType::HomeOwner, DEATH as u8);
+        assert_eq!(owner.home_value, 500000.0);
+        assert_eq!(owner.monthly_rent, 0.0);
+    }
+
+    #[test]
+    fn owner_pays_no_closing_costs_on_a_home_already_owned() {
+        let mut owner = Saver { ..saver() };
+        let savings = owner.calculate_savings(SaverType::HomeOwner, 31);
+        assert_eq!(savings[30], 200000.0);
+        assert_eq!(owner.home_cost_basis, 500000.0);
+    }
+
+    #[test]
+    fn owner_moves_back_to_renting_at_the_sale_age() {
+        let mut owner = Saver {
+            sale_age: Some(40),
+            ..saver()
+        };
+        let savings = owner.calculate_savings(SaverType::HomeOwner, 41);
+        assert_eq!(owner.home_value, 0.0);
+        assert_eq!(owner.mortgage_debt, 0.0);
+        assert_eq!(owner.monthly_rent, owner.rent_after_sale);
+        // selling costs on the 500k price, then a year of rent instead of the mortgage and home costs
+        assert!((savings[40] - (savings[39] - 30000.0 + 12.0 * 7000.0)).abs() < 1.0);
+    }
+
+    fn buy_later(total_savings: f32) -> Saver<'static> {
+        Saver {
+            total_savings,
+            home_value: 0.0,
+            mortgage_debt: 0.0,
+            monthly_rent: 2000.0,
+            monthly_insurance: 0.0,
+            purchase_age: Some(31),
+            purchase_home_value: 500000.0,
+            down_payment_rate: 0.2,
+            ..saver()
+        }
+    }
+
+    #[test]
+    fn buy_later_buys_at_the_purchase_age() {
+        let mut saver = buy_later(200000.0);
+        saver.calculate_savings(SaverType::BuyLater, 32);
+        assert_eq!(saver.purchase_age, Some(31));
+        assert_eq!(saver.home_value, 500000.0);
+        assert_eq!(saver.monthly_rent, 0.0);
+    }
+
+    #[test]
+    fn buy_later_waits_until_savings_cover_the_down_payment_and_closing_costs() {
+        // 115k is needed up front and 84k is saved a year
+        let mut saver = buy_later(30000.0);
+        let savings = saver.calculate_savings(SaverType::BuyLater, DEATH as u8);
+        assert_eq!(saver.purchase_age, Some(33));
+        assert_eq!(savings[30], 30000.0);
+        assert_eq!(savings[31], 114000.0);
+        assert_eq!(saver.home_value, 500000.0);
+    }
 }
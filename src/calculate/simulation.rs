@@ -0,0 +1,465 @@
+use super::{
+    assets::AssetModel,
+    consts::DEATH,
+    history,
+    household::Household,
+    rates::{new_rates, seeded_rng, Model, RatePath},
+    saver::{Saver, SaverType},
+    withdrawal::WithdrawalError,
+};
+
+pub const MONTE_CARLO_RUNS: usize = 1000;
+
+// savings percentiles by age across every run of the simulation
+#[derive(Clone, Debug, PartialEq)]
+pub struct Bands {
+    pub p10: Vec<f32>,
+    pub p50: Vec<f32>,
+    pub p90: Vec<f32>,
+}
+
+impl Default for Bands {
+    fn default() -> Self {
+        Bands {
+            p10: vec![0.0; DEATH],
+            p50: vec![0.0; DEATH],
+            p90: vec![0.0; DEATH],
+        }
+    }
+}
+
+impl Bands {
+    // collapse each age across runs into its 10th, 50th and 90th percentile
+    pub fn from_runs(runs: &[Vec<f32>]) -> Self {
+        // runs past the death age when a younger partner outlives the saver
+        let years = runs.iter().map(Vec::len).min().unwrap_or(DEATH).max(DEATH);
+        let mut bands = Bands {
+            p10: vec![0.0; years],
+            p50: vec![0.0; years],
+            p90: vec![0.0; years],
+        };
+        let mut column = Vec::with_capacity(runs.len());
+        for age in 0..years {
+            column.clear();
+            column.extend(runs.iter().map(|run| run.get(age).copied().unwrap_or(0.0)));
+            column.sort_by(f32::total_cmp);
+            bands.p10[age] = percentile(&column, 0.10);
+            bands.p50[age] = percentile(&column, 0.50);
+            bands.p90[age] = percentile(&column, 0.90);
+        }
+        bands
+    }
+}
+
+// nearest rank percentile of an already sorted slice
+pub fn percentile(sorted: &[f32], p: f32) -> f32 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = (p * sorted.len() as f32).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MonteCarlo {
+    pub owner: Bands,
+    pub renter: Bands,
+    pub buy_later: Bands,
+    // the owner without extra principal payments, investing the difference instead
+    pub owner_invest: Bands,
+    // monthly mortgage payment by age, shows the payment shocks of an adjustable rate
+    pub owner_payments: Bands,
+    pub buy_later_payments: Bands,
+    // per age median of the simulated rates, a representative single path
+    pub rates: RatePath,
+    // savers whose run stopped early because the withdrawal strategy failed, with the first failure
+    pub withdrawal_errors: usize,
+    pub first_withdrawal_error: Option<WithdrawalError>,
+    // how often each saver's savings lasted and their worst ending
+    pub owner_outcome: Outcome,
+    pub renter_outcome: Outcome,
+    pub buy_later_outcome: Outcome,
+    // the seed every rate path was drawn from, the same seed reproduces the same simulation
+    pub seed: u64,
+    // the first year of history each run replays, empty for simulated rates
+    pub start_years: Vec<u16>,
+}
+
+// the share of runs whose savings lasted until the end, and the run that ended with the least in today's dollars
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Outcome {
+    pub success_rate: f32,
+    pub worst_ending: f32,
+    pub worst_run: usize,
+}
+
+impl Outcome {
+    pub fn from_endings(endings: &[f32]) -> Self {
+        if endings.is_empty() {
+            return Outcome::default();
+        }
+        let (worst_run, worst_ending) = endings
+            .iter()
+            .copied()
+            .enumerate()
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .unwrap_or_default();
+        Outcome {
+            success_rate: endings.iter().filter(|ending| **ending > 0.0).count() as f32
+                / endings.len() as f32,
+            worst_ending,
+            worst_run,
+        }
+    }
+}
+
+// the number of years of rates a run needs, past the death age while a younger partner lives
+fn household_years(savers: [&Saver; 3]) -> usize {
+    savers
+        .iter()
+        .map(|saver| Household::last_age(*saver, DEATH as u8) as usize)
+        .max()
+        .unwrap_or(DEATH)
+}
+
+// run the savers over paths drawn from the seed with the return model
+pub fn monte_carlo(
+    owner: &Saver,
+    renter: &Saver,
+    buy_later: &Saver,
+    runs: usize,
+    seed: u64,
+    model: &Model,
+) -> MonteCarlo {
+    let mut rng = seeded_rng(seed);
+    let years = household_years([owner, renter, buy_later]);
+    let paths = (0..runs).map(|_| new_rates(&mut rng, years, model));
+    MonteCarlo {
+        seed,
+        ..simulate(owner, renter, buy_later, paths)
+    }
+}
+
+// run the savers over correlated asset paths drawn from the seed
+pub fn correlated_monte_carlo(
+    owner: &Saver,
+    renter: &Saver,
+    buy_later: &Saver,
+    runs: usize,
+    seed: u64,
+    assets: &AssetModel,
+) -> MonteCarlo {
+    let mut rng = seeded_rng(seed);
+    let years = household_years([owner, renter, buy_later]);
+    let paths = (0..runs).map(|_| assets.rates(&mut rng, years));
+    MonteCarlo {
+        seed,
+        ..simulate(owner, renter, buy_later, paths)
+    }
+}
+
+// run the savers over history from every start year the run fits in
+pub fn backtest(owner: &Saver, renter: &Saver, buy_later: &Saver) -> MonteCarlo {
+    let years = household_years([owner, renter, buy_later]);
+    let start_years = history::start_years(years.saturating_sub(owner.current_age as usize + 1));
+    let paths = start_years
+        .iter()
+        .map(|start_year| history::historical_rates(*start_year, owner.current_age, years));
+    MonteCarlo {
+        start_years: start_years.clone(),
+        ..simulate(owner, renter, buy_later, paths)
+    }
+}
+
+// run the owner, renter and buy later savers over the same rate path for each path and aggregate the results
+fn simulate(
+    owner: &Saver,
+    renter: &Saver,
+    buy_later: &Saver,
+    paths: impl ExactSizeIterator<Item = RatePath>,
+) -> MonteCarlo {
+    let runs = paths.len();
+    let mut owner_runs = Vec::with_capacity(runs);
+    let mut renter_runs = Vec::with_capacity(runs);
+    let mut buy_later_runs = Vec::with_capacity(runs);
+    let mut owner_invest_runs = Vec::with_capacity(runs);
+    let owner_invest = prepay_alternative(owner);
+    let mut owner_payment_runs = Vec::with_capacity(runs);
+    let mut buy_later_payment_runs = Vec::with_capacity(runs);
+    let mut owner_endings = Vec::with_capacity(runs);
+    let mut renter_endings = Vec::with_capacity(runs);
+    let mut buy_later_endings = Vec::with_capacity(runs);
+    let mut interest_runs = Vec::with_capacity(runs);
+    let mut inflation_runs = Vec::with_capacity(runs);
+    let mut mortgage_runs = Vec::with_capacity(runs);
+    let mut index_runs = Vec::with_capacity(runs);
+    let mut market_shock_runs = Vec::with_capacity(runs);
+    let mut wage_noise_runs = Vec::with_capacity(runs);
+    let mut rent_growth_runs = Vec::with_capacity(runs);
+    let mut home_growth_runs = Vec::with_capacity(runs);
+    let mut stock_runs = Vec::with_capacity(runs);
+    let mut bond_runs = Vec::with_capacity(runs);
+    let mut cash_runs = Vec::with_capacity(runs);
+    let mut withdrawal_errors = Vec::new();
+
+    for mut rates in paths {
+        // every saver shares the allocation, savings earn its mix of the asset returns
+        rates.interest = owner.allocation.portfolio_returns(
+            &rates,
+            owner.current_age,
+            owner.retirement_age,
+            owner.annual_rebalancing,
+        );
+        let mut run_owner = owner.with_rates(&rates);
+        owner_runs.push(run_owner.calculate_savings(SaverType::HomeOwner, DEATH as u8));
+        if let Some(owner_invest) = &owner_invest {
+            owner_invest_runs.push(
+                owner_invest
+                    .with_rates(&rates)
+                    .calculate_savings(SaverType::HomeOwner, DEATH as u8),
+            );
+        }
+        let mut run_renter = renter.with_rates(&rates);
+        renter_runs.push(run_renter.calculate_savings(SaverType::Renter, DEATH as u8));
+        let mut run_buy_later = buy_later.with_rates(&rates);
+        buy_later_runs.push(run_buy_later.calculate_savings(SaverType::BuyLater, DEATH as u8));
+        withdrawal_errors.extend(
+            [&run_owner, &run_renter, &run_buy_later]
+                .iter()
+                .filter_map(|saver| saver.withdrawal_error),
+        );
+        owner_endings.push(real_ending(&run_owner.home_savings, &run_owner));
+        renter_endings.push(real_ending(&run_renter.rental_savings, &run_renter));
+        buy_later_endings.push(real_ending(
+            &run_buy_later.buy_later_savings,
+            &run_buy_later,
+        ));
+        owner_payment_runs.push(run_owner.mortgage_payments);
+        buy_later_payment_runs.push(run_buy_later.mortgage_payments);
+        interest_runs.push(rates.interest);
+        inflation_runs.push(rates.inflation);
+        mortgage_runs.push(rates.mortgage);
+        index_runs.push(rates.index);
+        market_shock_runs.push(rates.market_shock);
+        wage_noise_runs.push(rates.wage_noise);
+        rent_growth_runs.push(rates.rent_growth);
+        home_growth_runs.push(rates.home_growth);
+        stock_runs.push(rates.stocks);
+        bond_runs.push(rates.bonds);
+        cash_runs.push(rates.cash);
+    }
+
+    MonteCarlo {
+        owner: Bands::from_runs(&owner_runs),
+        renter: Bands::from_runs(&renter_runs),
+        buy_later: Bands::from_runs(&buy_later_runs),
+        owner_invest: Bands::from_runs(&owner_invest_runs),
+        owner_payments: Bands::from_runs(&owner_payment_runs),
+        buy_later_payments: Bands::from_runs(&buy_later_payment_runs),
+        rates: RatePath {
+            interest: Bands::from_runs(&interest_runs).p50,
+            inflation: Bands::from_runs(&inflation_runs).p50,
+            mortgage: Bands::from_runs(&mortgage_runs).p50,
+            index: Bands::from_runs(&index_runs).p50,
+            market_shock: Bands::from_runs(&market_shock_runs).p50,
+            wage_noise: Bands::from_runs(&wage_noise_runs).p50,
+            rent_growth: median_path(&rent_growth_runs),
+            home_growth: median_path(&home_growth_runs),
+            stocks: median_path(&stock_runs),
+            bonds: median_path(&bond_runs),
+            cash: median_path(&cash_runs),
+        },
+        withdrawal_errors: withdrawal_errors.len(),
+        first_withdrawal_error: withdrawal_errors.first().copied(),
+        owner_outcome: Outcome::from_endings(&owner_endings),
+        renter_outcome: Outcome::from_endings(&renter_endings),
+        buy_later_outcome: Outcome::from_endings(&buy_later_endings),
+        ..MonteCarlo::default()
+    }
+}
+
+// per age median of the runs, stays empty when the paths don't have the rate
+fn median_path(runs: &[Vec<f32>]) -> Vec<f32> {
+    if runs.iter().all(Vec::is_empty) {
+        vec![]
+    } else {
+        Bands::from_runs(runs).p50
+    }
+}
+
+// savings at the end of the run in today's dollars, nothing when they ran out
+fn real_ending(savings: &[f32], saver: &Saver) -> f32 {
+    savings.last().copied().unwrap_or(0.0) / saver.price_level
+}
+
+// the same owner without extra principal payments, None if the owner doesn't prepay
+pub fn prepay_alternative<'a>(owner: &Saver<'a>) -> Option<Saver<'a>> {
+    if owner.extra_monthly_principal > 0.0 || owner.extra_annual_principal > 0.0 {
+        Some(Saver {
+            extra_monthly_principal: 0.0,
+            extra_annual_principal: 0.0,
+            ..owner.clone()
+        })
+    } else {
+        None
+    }
+}
+
+// payoff ages and interest paid with and without prepaying the mortgage
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PrepayComparison {
+    pub payoff_age: Option<u8>,
+    pub invest_payoff_age: Option<u8>,
+    pub interest_paid: f32,
+    pub invest_interest_paid: f32,
+}
+
+impl PrepayComparison {
+    pub fn interest_saved(&self) -> f32 {
+        self.invest_interest_paid - self.interest_paid
+    }
+}
+
+// compare prepaying against investing on a single rate path
+pub fn prepay_comparison(owner: &Saver, rates: &RatePath) -> PrepayComparison {
+    let mut prepay = owner.with_rates(rates);
+    prepay.calculate_savings(SaverType::HomeOwner, DEATH as u8);
+    let mut invest = Saver {
+        extra_monthly_principal: 0.0,
+        extra_annual_principal: 0.0,
+        ..owner.with_rates(rates)
+    };
+    invest.calculate_savings(SaverType::HomeOwner, DEATH as u8);
+    PrepayComparison {
+        payoff_age: prepay.home_owned_age,
+        invest_payoff_age: invest.home_owned_age,
+        interest_paid: prepay.mortgage_interest_paid,
+        invest_interest_paid: invest.mortgage_interest_paid,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calculate::{household::Partner, saver::tests::saver};
+
+    #[test]
+    fn percentile_takes_the_nearest_rank() {
+        let sorted: Vec<f32> = (1..=10).map(|value| value as f32).collect();
+        assert_eq!(percentile(&sorted, 0.10), 1.0);
+        assert_eq!(percentile(&sorted, 0.50), 5.0);
+        assert_eq!(percentile(&sorted, 0.90), 9.0);
+        assert_eq!(percentile(&sorted, 0.0), 1.0);
+        assert_eq!(percentile(&sorted, 1.0), 10.0);
+        assert_eq!(percentile(&[], 0.5), 0.0);
+    }
+
+    #[test]
+    fn bands_take_percentiles_across_runs_at_each_age() {
+        // run i holds i at every age, listed out of order
+        let runs: Vec<Vec<f32>> = (1..=10).rev().map(|run| vec![run as f32; DEATH]).collect();
+        let bands = Bands::from_runs(&runs);
+        assert_eq!(bands.p10, vec![1.0; DEATH]);
+        assert_eq!(bands.p50, vec![5.0; DEATH]);
+        assert_eq!(bands.p90, vec![9.0; DEATH]);
+    }
+
+    #[test]
+    fn bands_run_past_death_as_long_as_the_shortest_run() {
+        let runs = vec![vec![1.0; DEATH + 5], vec![2.0; DEATH + 3]];
+        assert_eq!(Bands::from_runs(&runs).p50.len(), DEATH + 3);
+        assert_eq!(Bands::from_runs(&[]).p50.len(), DEATH);
+    }
+
+    #[test]
+    fn monte_carlo_bands_are_ordered() {
+        let renter = Saver {
+            home_value: 0.0,
+            mortgage_debt: 0.0,
+            monthly_rent: 2500.0,
+            ..saver()
+        };
+        let simulation = monte_carlo(&saver(), &renter, &renter, 20, 7, &Model::default());
+        for bands in [&simulation.owner, &simulation.renter, &simulation.buy_later] {
+            for age in 0..DEATH {
+                assert!(bands.p10[age] <= bands.p50[age] && bands.p50[age] <= bands.p90[age]);
+            }
+        }
+        assert_eq!(simulation.rates.interest.len(), DEATH);
+    }
+
+    #[test]
+    fn the_same_seed_reproduces_the_simulation() {
+        let first = monte_carlo(&saver(), &saver(), &saver(), 10, 42, &Model::default());
+        let again = monte_carlo(&saver(), &saver(), &saver(), 10, 42, &Model::default());
+        assert_eq!(first.owner, again.owner);
+        assert_eq!(first.rates, again.rates);
+        assert_eq!(again.seed, 42);
+        let other = monte_carlo(&saver(), &saver(), &saver(), 10, 43, &Model::default());
+        assert_ne!(first.rates, other.rates);
+    }
+
+    #[test]
+    fn prepaying_pays_off_sooner_with_less_interest() {
+        let owner = Saver {
+            extra_monthly_principal: 1000.0,
+            ..saver()
+        };
+        let flat = RatePath {
+            interest: vec![0.0; DEATH],
+            inflation: vec![0.0; DEATH],
+            mortgage: vec![0.0; DEATH],
+            index: vec![0.0; DEATH],
+            market_shock: vec![0.0; DEATH],
+            wage_noise: vec![0.0; DEATH],
+            home_growth: vec![0.0; DEATH],
+            rent_growth: vec![0.0; DEATH],
+            stocks: vec![0.0; DEATH],
+            bonds: vec![0.0; DEATH],
+            cash: vec![0.0; DEATH],
+        };
+        let comparison = prepay_comparison(&owner, &flat);
+        assert!(comparison.payoff_age < comparison.invest_payoff_age);
+        assert!(comparison.interest_saved() > 0.0);
+        assert!(prepay_alternative(&saver()).is_none());
+    }
+
+    #[test]
+    fn backtest_replays_every_start_year_that_fits() {
+        let renter = Saver {
+            home_value: 0.0,
+            mortgage_debt: 0.0,
+            monthly_rent: 2500.0,
+            ..saver()
+        };
+        let broke = Saver {
+            monthly_rent: 20000.0,
+            ..renter.clone()
+        };
+        let backtest = backtest(&saver(), &renter, &broke);
+        // 69 years from 31 through 99 fit from 1928 to 1955
+        assert_eq!(backtest.start_years, (1928..=1955).collect::<Vec<_>>());
+        assert_eq!(backtest.renter_outcome.success_rate, 1.0);
+        assert_eq!(backtest.buy_later_outcome.success_rate, 0.0);
+    }
+
+    #[test]
+    fn backtest_has_no_runs_when_the_household_outlives_the_history() {
+        let couple = Saver {
+            partner: Some(Partner {
+                current_age: 0,
+                retirement_age: 65,
+                monthly_income: 0.0,
+                social_security_age: 67,
+                social_security_benefit: 0.0,
+                pension_age: 65,
+                pension_benefit: 0.0,
+                pension_cola: false,
+            }),
+            ..saver()
+        };
+        let backtest = backtest(&couple, &couple, &couple);
+        assert!(backtest.start_years.is_empty());
+        assert_eq!(backtest.owner_outcome, Outcome::default());
+    }
+}
@@ -0,0 +1,130 @@
+use super::{events::LifeEvents, owner::Owner, saver::Saver};
+
+// yearly real change in retirement spending by age and real annual spending (blanchett's retirement spending smile),
+// spending falls through the early retirement years and picks back up late in life
+pub fn spending_smile(age: u8, real_annual_spending: f32) -> f32 {
+    let age = age as f32;
+    0.00008 * age.powi(2) - 0.0125 * age - 0.0066 * real_annual_spending.max(1.0).ln() + 0.546
+}
+
+pub trait RetirementSpending<Saver> {
+    fn monthly_healthcare(&self) -> f32;
+    fn monthly_housing(&self) -> f32;
+    fn monthly_needs(&self) -> f32;
+    fn retire(&mut self);
+    fn apply_spending_smile(&mut self);
+    fn inflate_healthcare(&mut self, monthly_inflation: f32);
+}
+
+impl RetirementSpending<Saver<'_>> for Saver<'_> {
+    // healthcare costs are paid out of pocket once retired
+    fn monthly_healthcare(&self) -> f32 {
+        if self.active_retirement {
+            self.healthcare_cost
+        } else {
+            0.0
+        }
+    }
+
+    // rent, home costs and the mortgage payment
+    fn monthly_housing(&self) -> f32 {
+        let mortgage_payment = if self.mortgage_debt > 0.0 {
+            self.cached_mortgage_installment
+                .unwrap_or(0.0)
+                .min(self.mortgage_debt + self.monthly_mortgage_interest_payment())
+        } else {
+            0.0
+        };
+        self.monthly_rent + self.monthly_home_expenses() + mortgage_payment
+    }
+
+    // spending that has to be paid whatever the withdrawal strategy says
+    fn monthly_needs(&self) -> f32 {
+        self.monthly_expenses
+            + self.monthly_event_expenses()
+            + self.monthly_healthcare()
+            + self.monthly_housing()
+    }
+
+    // living expenses move to a percent of what they were before retirement
+    fn retire(&mut self) {
+        self.monthly_expenses *= self.retirement_expense_rate;
+    }
+
+    fn apply_spending_smile(&mut self) {
+        if self.spending_smile {
+            let real_annual_spending = self.monthly_expenses * 12.0 / self.price_level;
+            self.monthly_expenses *= 1.0 + spending_smile(self.current_age, real_annual_spending);
+        }
+    }
+
+    // healthcare grows faster than inflation by the excess healthcare inflation
+    fn inflate_healthcare(&mut self, monthly_inflation: f32) {
+        self.healthcare_cost *= 1.0 + monthly_inflation + self.healthcare_inflation / 12.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calculate::saver::tests::saver;
+
+    fn assert_close(actual: f32, expected: f32) {
+        assert!(
+            (actual - expected).abs() < 0.0001,
+            "{} != {}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn spending_smile_dips_through_retirement_and_rises_late() {
+        // flat at 65, bottoms out near 78 and turns up again in the 90s on 50k a year
+        assert_close(spending_smile(65, 50000.0), 0.0001);
+        assert_close(spending_smile(78, 50000.0), -0.0137);
+        assert_close(spending_smile(95, 50000.0), 0.0091);
+        for age in 66..78 {
+            assert!(spending_smile(age, 50000.0) < spending_smile(age - 1, 50000.0));
+        }
+        for age in 79..100 {
+            assert!(spending_smile(age, 50000.0) > spending_smile(age - 1, 50000.0));
+        }
+    }
+
+    #[test]
+    fn bigger_spenders_cut_back_faster() {
+        assert!(spending_smile(75, 100000.0) < spending_smile(75, 50000.0));
+    }
+
+    #[test]
+    fn spending_smile_only_applies_when_enabled() {
+        let mut retiree = Saver {
+            current_age: 78,
+            monthly_expenses: 50000.0 / 12.0,
+            ..saver()
+        };
+        retiree.apply_spending_smile();
+        assert_eq!(retiree.monthly_expenses, 50000.0 / 12.0);
+        retiree.spending_smile = true;
+        retiree.apply_spending_smile();
+        assert!((retiree.monthly_expenses - 50000.0 / 12.0 * (1.0 - 0.0137)).abs() < 0.1);
+    }
+
+    #[test]
+    fn retiring_keeps_a_percent_of_living_expenses() {
+        let mut retiree = Saver {
+            retirement_expense_rate: 0.8,
+            healthcare_cost: 500.0,
+            healthcare_inflation: 0.024,
+            ..saver()
+        };
+        assert_eq!(retiree.monthly_healthcare(), 0.0);
+        retiree.retire();
+        retiree.active_retirement = true;
+        assert_eq!(retiree.monthly_expenses, 4000.0);
+        assert_eq!(retiree.monthly_healthcare(), 500.0);
+        retiree.inflate_healthcare(0.001);
+        assert!((retiree.healthcare_cost - 501.5).abs() < 0.01);
+    }
+}
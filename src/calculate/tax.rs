@@ -0,0 +1,380 @@
+use super::{
+    accounts::Accounts, benefits::Benefits, events::LifeEvents, owner::Owner, saver::Saver,
+};
+
+// federal brackets in today's dollars as (top of bracket, rate)
+pub const SINGLE_BRACKETS: [(f32, f32); 7] = [
+    (11600.0, 0.10),
+    (47150.0, 0.12),
+    (100525.0, 0.22),
+    (191950.0, 0.24),
+    (243725.0, 0.32),
+    (609350.0, 0.35),
+    (f32::INFINITY, 0.37),
+];
+pub const MARRIED_BRACKETS: [(f32, f32); 7] = [
+    (23200.0, 0.10),
+    (94300.0, 0.12),
+    (201050.0, 0.22),
+    (383900.0, 0.24),
+    (487450.0, 0.32),
+    (731200.0, 0.35),
+    (f32::INFINITY, 0.37),
+];
+// long term capital gains brackets, stacked on top of ordinary taxable income
+pub const SINGLE_CAPITAL_GAINS_BRACKETS: [(f32, f32); 3] =
+    [(47025.0, 0.0), (518900.0, 0.15), (f32::INFINITY, 0.20)];
+pub const MARRIED_CAPITAL_GAINS_BRACKETS: [(f32, f32); 3] =
+    [(94050.0, 0.0), (583750.0, 0.15), (f32::INFINITY, 0.20)];
+pub const SINGLE_STANDARD_DEDUCTION: f32 = 14600.0;
+pub const MARRIED_STANDARD_DEDUCTION: f32 = 29200.0;
+// state and local taxes deductible when itemizing
+pub const SALT_CAP: f32 = 10000.0;
+// social security (up to the wage base) and medicare
+pub const SOCIAL_SECURITY_TAX: f32 = 0.062;
+pub const SOCIAL_SECURITY_WAGE_BASE: f32 = 168600.0;
+pub const MEDICARE_TAX: f32 = 0.0145;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FilingStatus {
+    Single,
+    MarriedJoint,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TaxConfig {
+    pub filing_status: FilingStatus,
+    // flat state income tax
+    pub state_tax_rate: f32,
+}
+
+// annual income by how it is taxed
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TaxableIncome {
+    // each earner's wages, the social security wage base applies to each on their own
+    pub wages: [f32; 2],
+    // 401k contributions come out of wages before income tax but not payroll tax
+    pub pre_tax_contributions: f32,
+    // pre-tax account withdrawals and the taxable part of benefits
+    pub ordinary: f32,
+    // realized long term gains
+    pub capital_gains: f32,
+}
+
+// annual itemizable expenses
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Deductions {
+    pub mortgage_interest: f32,
+    pub property_tax: f32,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TaxBill {
+    pub federal: f32,
+    pub capital_gains: f32,
+    pub state: f32,
+    pub payroll: f32,
+    pub itemized: bool,
+}
+
+impl TaxBill {
+    pub fn total(&self) -> f32 {
+        self.federal + self.capital_gains + self.state + self.payroll
+    }
+}
+
+impl TaxConfig {
+    pub fn brackets(&self) -> &'static [(f32, f32); 7] {
+        match self.filing_status {
+            FilingStatus::Single => &SINGLE_BRACKETS,
+            FilingStatus::MarriedJoint => &MARRIED_BRACKETS,
+        }
+    }
+
+    pub fn capital_gains_brackets(&self) -> &'static [(f32, f32); 3] {
+        match self.filing_status {
+            FilingStatus::Single => &SINGLE_CAPITAL_GAINS_BRACKETS,
+            FilingStatus::MarriedJoint => &MARRIED_CAPITAL_GAINS_BRACKETS,
+        }
+    }
+
+    pub fn standard_deduction(&self) -> f32 {
+        match self.filing_status {
+            FilingStatus::Single => SINGLE_STANDARD_DEDUCTION,
+            FilingStatus::MarriedJoint => MARRIED_STANDARD_DEDUCTION,
+        }
+    }
+
+    // annual taxes in nominal dollars, brackets and deductions are scaled by the price level
+    pub fn annual_tax(
+        &self,
+        income: &TaxableIncome,
+        deductions: &Deductions,
+        price_level: f32,
+    ) -> TaxBill {
+        let wages: f32 = income.wages.iter().sum();
+        let earned = wages - income.pre_tax_contributions + income.ordinary;
+        let state = (earned + income.capital_gains) * self.state_tax_rate;
+        let standard = self.standard_deduction() * price_level;
+        // take the larger of the standard deduction or itemizing
+        let itemized = deductions.mortgage_interest
+            + (deductions.property_tax + state).min(SALT_CAP * price_level);
+        let deduction = standard.max(itemized);
+        // the deduction comes out of ordinary income first, anything left over reduces gains
+        let taxable = (earned - deduction).max(0.0);
+        let taxable_gains = (income.capital_gains - (deduction - earned).max(0.0)).max(0.0);
+
+        let payroll = income
+            .wages
+            .iter()
+            .map(|wages| {
+                wages.min(SOCIAL_SECURITY_WAGE_BASE * price_level) * SOCIAL_SECURITY_TAX
+                    + wages * MEDICARE_TAX
+            })
+            .sum();
+
+        TaxBill {
+            federal: stacked_tax(self.brackets(), 0.0, taxable, price_level),
+            capital_gains: stacked_tax(
+                self.capital_gains_brackets(),
+                taxable,
+                taxable_gains,
+                price_level,
+            ),
+            state,
+            payroll,
+            itemized: itemized > standard,
+        }
+    }
+}
+
+// tax on the amount sitting on top of the income below it, bracket tops are scaled by the price level
+pub fn stacked_tax(brackets: &[(f32, f32)], below: f32, amount: f32, price_level: f32) -> f32 {
+    let mut tax = 0.0;
+    let mut bottom = below;
+    for (top, rate) in brackets {
+        let top = (top * price_level).min(below + amount);
+        if top > bottom {
+            tax += (top - bottom) * rate;
+            bottom = top;
+        }
+    }
+    tax
+}
+
+pub trait Taxpayer<Saver> {
+    fn annual_taxes_with(&self, ordinary: f32, capital_gains: f32) -> TaxBill;
+    fn annual_taxes(&self) -> TaxBill;
+    fn monthly_taxes(&self) -> f32;
+    fn ordinary_income_tax(&self, ordinary: f32) -> f32;
+    fn capital_gains_tax(&self, capital_gains: f32) -> f32;
+}
+
+impl Taxpayer<Saver<'_>> for Saver<'_> {
+    // annualize this month's income and deductions, withdrawals and gains are already annual
+    fn annual_taxes_with(&self, ordinary: f32, capital_gains: f32) -> TaxBill {
+        match &self.tax {
+            Some(tax) => tax.annual_tax(
+                &TaxableIncome {
+                    wages: if self.active_retirement {
+                        [0.0; 2]
+                    } else {
+                        self.monthly_member_wages().map(|wages| wages * 12.0)
+                    },
+                    pre_tax_contributions: self.monthly_pre_tax_contribution() * 12.0,
+                    ordinary: ordinary + self.taxable_benefits() * 12.0,
+                    capital_gains,
+                },
+                &Deductions {
+                    mortgage_interest: self.monthly_mortgage_interest_payment().max(0.0) * 12.0,
+                    property_tax: self.monthly_home_costs().property_tax * 12.0,
+                },
+                self.price_level,
+            ),
+            None => TaxBill::default(),
+        }
+    }
+
+    fn annual_taxes(&self) -> TaxBill {
+        self.annual_taxes_with(0.0, 0.0)
+    }
+
+    fn monthly_taxes(&self) -> f32 {
+        self.annual_taxes().total() / 12.0
+    }
+
+    // the extra tax owed for a year of pre-tax withdrawals
+    fn ordinary_income_tax(&self, ordinary: f32) -> f32 {
+        if ordinary <= 0.0 {
+            return 0.0;
+        }
+        self.annual_taxes_with(ordinary, 0.0).total() - self.annual_taxes().total()
+    }
+
+    // the extra tax owed for realizing these gains in a year
+    fn capital_gains_tax(&self, capital_gains: f32) -> f32 {
+        if capital_gains <= 0.0 {
+            return 0.0;
+        }
+        self.annual_taxes_with(0.0, capital_gains).total() - self.annual_taxes().total()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calculate::saver::tests::saver;
+
+    const SINGLE: TaxConfig = TaxConfig {
+        filing_status: FilingStatus::Single,
+        state_tax_rate: 0.0,
+    };
+
+    fn assert_close(actual: f32, expected: f32) {
+        assert!(
+            (actual - expected).abs() < 0.01,
+            "{actual} is not {expected}"
+        );
+    }
+
+    fn wages(wages: f32) -> TaxableIncome {
+        TaxableIncome {
+            wages: [wages, 0.0],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn wages_are_taxed_after_the_standard_deduction() {
+        let bill = SINGLE.annual_tax(&wages(100000.0), &Deductions::default(), 1.0);
+        // 85,400 taxable after the 14,600 deduction: 10% of 11,600, 12% up to 47,150 and 22% of the rest
+        assert_close(bill.federal, 13841.0);
+        assert_close(bill.payroll, 7650.0);
+        assert_eq!(bill.state, 0.0);
+        assert!(!bill.itemized);
+    }
+
+    #[test]
+    fn married_couples_use_the_joint_brackets() {
+        let married = TaxConfig {
+            filing_status: FilingStatus::MarriedJoint,
+            ..SINGLE
+        };
+        let bill = married.annual_tax(&wages(100000.0), &Deductions::default(), 1.0);
+        // 70,800 taxable after the 29,200 deduction
+        assert_close(bill.federal, 8032.0);
+    }
+
+    #[test]
+    fn itemizes_when_it_beats_the_standard_deduction() {
+        let deductions = Deductions {
+            mortgage_interest: 20000.0,
+            property_tax: 5000.0,
+        };
+        let bill = SINGLE.annual_tax(&wages(100000.0), &deductions, 1.0);
+        // 75,000 taxable after 25,000 itemized
+        assert!(bill.itemized);
+        assert_close(bill.federal, 11553.0);
+    }
+
+    #[test]
+    fn state_and_property_taxes_are_deducted_up_to_the_salt_cap() {
+        let with_state_tax = TaxConfig {
+            state_tax_rate: 0.05,
+            ..SINGLE
+        };
+        let deductions = Deductions {
+            mortgage_interest: 10000.0,
+            property_tax: 8000.0,
+        };
+        let bill = with_state_tax.annual_tax(&wages(100000.0), &deductions, 1.0);
+        // 13,000 of state and property taxes capped at 10,000, 80,000 taxable
+        assert_close(bill.state, 5000.0);
+        assert!(bill.itemized);
+        assert_close(bill.federal, 12653.0);
+    }
+
+    #[test]
+    fn brackets_and_deductions_grow_with_the_price_level() {
+        let today = SINGLE.annual_tax(&wages(100000.0), &Deductions::default(), 1.0);
+        let doubled = SINGLE.annual_tax(&wages(200000.0), &Deductions::default(), 2.0);
+        assert_close(doubled.federal, 2.0 * today.federal);
+    }
+
+    #[test]
+    fn social_security_stops_at_the_wage_base() {
+        let bill = SINGLE.annual_tax(&wages(200000.0), &Deductions::default(), 1.0);
+        assert_close(bill.payroll, 168600.0 * 0.062 + 200000.0 * 0.0145);
+    }
+
+    #[test]
+    fn each_earner_has_their_own_wage_base() {
+        let couple = TaxableIncome {
+            wages: [100000.0, 100000.0],
+            ..Default::default()
+        };
+        let split = SINGLE.annual_tax(&couple, &Deductions::default(), 1.0);
+        assert_close(split.payroll, 200000.0 * 0.0765);
+        let one_earner = SINGLE.annual_tax(&wages(200000.0), &Deductions::default(), 1.0);
+        assert!(one_earner.payroll < split.payroll);
+        assert_close(split.federal, one_earner.federal);
+    }
+
+    #[test]
+    fn saver_taxes_are_a_twelfth_of_the_annualized_month() {
+        let mut taxpayer = Saver {
+            tax: Some(SINGLE),
+            ..saver()
+        };
+        assert_close(
+            taxpayer.monthly_taxes(),
+            taxpayer.annual_taxes().total() / 12.0,
+        );
+        assert!(taxpayer.annual_taxes().federal > 0.0);
+        taxpayer.active_retirement = true;
+        assert_eq!(taxpayer.annual_taxes().payroll, 0.0);
+        assert_eq!(saver().monthly_taxes(), 0.0);
+    }
+
+    #[test]
+    fn stacked_tax_fills_each_bracket_in_turn() {
+        assert_close(stacked_tax(&SINGLE_BRACKETS, 0.0, 50000.0, 1.0), 6053.0);
+        assert_close(stacked_tax(&SINGLE_BRACKETS, 0.0, 11600.0, 1.0), 1160.0);
+        assert_eq!(stacked_tax(&SINGLE_BRACKETS, 0.0, 0.0, 1.0), 0.0);
+    }
+
+    #[test]
+    fn stacked_tax_starts_above_the_income_below() {
+        // gains on top of 40,000 of income, 0% up to 47,025 then 15%
+        assert_close(
+            stacked_tax(&SINGLE_CAPITAL_GAINS_BRACKETS, 40000.0, 20000.0, 1.0),
+            1946.25,
+        );
+        assert_close(stacked_tax(&SINGLE_BRACKETS, 0.0, 23200.0, 2.0), 2320.0);
+    }
+
+    #[test]
+    fn gains_are_taxed_on_top_of_wages() {
+        let income = TaxableIncome {
+            wages: [54600.0, 0.0],
+            capital_gains: 20000.0,
+            ..Default::default()
+        };
+        let bill = SINGLE.annual_tax(&income, &Deductions::default(), 1.0);
+        // 40,000 of taxable wages leave 7,025 of the 0% gains bracket
+        assert_close(bill.capital_gains, 1946.25);
+    }
+
+    #[test]
+    fn unused_deduction_reduces_gains() {
+        let income = TaxableIncome {
+            wages: [0.0, 0.0],
+            capital_gains: 61625.0,
+            ..Default::default()
+        };
+        let bill = SINGLE.annual_tax(&income, &Deductions::default(), 1.0);
+        // 47,025 of gains left after the deduction, all in the 0% bracket
+        assert_eq!(bill.federal, 0.0);
+        assert_eq!(bill.capital_gains, 0.0);
+    }
+}
@@ -0,0 +1,126 @@
+use super::saver::Saver;
+
+// careers are assumed to start here for the hump shaped earnings curve
+pub const CAREER_START_AGE: u8 = 25;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum WageGrowth {
+    // income only keeps up with inflation
+    Flat,
+    // the same real raise every year
+    Linear { rate: f32 },
+    // real raises start at the early growth and shrink to nothing by the peak age, then turn into real pay cuts
+    Hump { early_growth: f32, peak_age: u8 },
+}
+
+impl WageGrowth {
+    // expected yearly real wage growth at the age
+    pub fn growth(&self, age: u8) -> f32 {
+        match self {
+            WageGrowth::Flat => 0.0,
+            WageGrowth::Linear { rate } => *rate,
+            WageGrowth::Hump {
+                early_growth,
+                peak_age,
+            } => {
+                let career = peak_age.saturating_sub(CAREER_START_AGE).max(1) as f32;
+                let age = age.max(CAREER_START_AGE) as f32;
+                early_growth * (*peak_age as f32 - age) / career
+            }
+        }
+    }
+}
+
+pub trait Wages<Saver> {
+    fn wage_shock(&self) -> f32;
+    fn real_wage_growth(&self) -> f32;
+}
+
+impl Wages<Saver<'_>> for Saver<'_> {
+    // this year's income shock, the correlation sets how much of it follows the market
+    fn wage_shock(&self) -> f32 {
+        let age = self.current_age as usize;
+        match (self.market_shocks.get(age), self.wage_noise.get(age)) {
+            (Some(market), Some(noise)) => {
+                let correlation = self.wage_market_correlation.clamp(-1.0, 1.0);
+                self.wage_volatility
+                    * (correlation * market + (1.0 - correlation.powi(2)).sqrt() * noise)
+            }
+            _ => 0.0,
+        }
+    }
+
+    fn real_wage_growth(&self) -> f32 {
+        self.wage_growth.growth(self.current_age) + self.wage_shock()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calculate::{consts::DEATH, saver::tests::saver};
+
+    fn assert_close(actual: f32, expected: f32) {
+        assert!(
+            (actual - expected).abs() < 0.0001,
+            "{} != {}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn flat_and_linear_growth_ignore_age() {
+        let linear = WageGrowth::Linear { rate: 0.01 };
+        for age in [25, 45, 64] {
+            assert_eq!(WageGrowth::Flat.growth(age), 0.0);
+            assert_eq!(linear.growth(age), 0.01);
+        }
+    }
+
+    #[test]
+    fn hump_growth_shrinks_to_the_peak_and_then_turns_negative() {
+        let hump = WageGrowth::Hump {
+            early_growth: 0.04,
+            peak_age: 50,
+        };
+        assert_close(hump.growth(25), 0.04);
+        // no faster than the early growth before a career starts
+        assert_close(hump.growth(20), 0.04);
+        assert_close(hump.growth(40), 0.016);
+        assert_close(hump.growth(50), 0.0);
+        assert_close(hump.growth(60), -0.016);
+    }
+
+    #[test]
+    fn wage_shocks_mix_the_market_and_noise_by_the_correlation() {
+        let mut earner = Saver {
+            market_shocks: &[1.0; DEATH],
+            wage_noise: &[-1.0; DEATH],
+            wage_volatility: 0.1,
+            ..saver()
+        };
+        // uncorrelated shocks are all noise
+        assert_close(earner.wage_shock(), -0.1);
+        earner.wage_market_correlation = 1.0;
+        assert_close(earner.wage_shock(), 0.1);
+        earner.wage_market_correlation = 0.6;
+        assert_close(earner.wage_shock(), 0.1 * (0.6 - 0.8));
+        // held to a valid correlation
+        earner.wage_market_correlation = 3.0;
+        assert_close(earner.wage_shock(), 0.1);
+        earner.wage_growth = WageGrowth::Linear { rate: 0.02 };
+        assert_close(earner.real_wage_growth(), 0.12);
+    }
+
+    #[test]
+    fn no_shock_without_a_draw_for_the_age() {
+        let earner = Saver {
+            market_shocks: &[],
+            wage_noise: &[],
+            wage_volatility: 0.1,
+            ..saver()
+        };
+        assert_eq!(earner.wage_shock(), 0.0);
+    }
+}
@@ -0,0 +1,286 @@
+use std::fmt;
+
+use super::{accounts::rmd_divisor, consts::DEATH, household::Household, saver::Saver};
+
+pub const STD_WITHDRAWAL_RATE: f32 = 0.04;
+// guyton klinger cuts or raises spending by the adjustment once the withdrawal rate moves past the guardrail
+pub const GUARDRAIL: f32 = 0.20;
+pub const GUARDRAIL_ADJUSTMENT: f32 = 0.10;
+// expected real return a variable percentage withdrawal amortizes savings over
+pub const VPW_REAL_RETURN: f32 = 0.04;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum WithdrawalError {
+    NotANumber { age: u8 },
+}
+
+impl fmt::Display for WithdrawalError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WithdrawalError::NotANumber { age } => {
+                write!(f, "the withdrawal at age {} was not a number", age)
+            }
+        }
+    }
+}
+
+pub trait WithdrawalStrategy {
+    // nominal spending out of savings for the coming year of retirement
+    fn annual_withdrawal(&self, saver: &Saver) -> Result<f32, WithdrawalError>;
+}
+
+fn checked(saver: &Saver, withdrawal: f32) -> Result<f32, WithdrawalError> {
+    if withdrawal.is_nan() {
+        Err(WithdrawalError::NotANumber {
+            age: saver.current_age,
+        })
+    } else {
+        Ok(withdrawal.max(0.0))
+    }
+}
+
+// a percent of savings in the first year of retirement, then the same spending grown with inflation
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ConstantDollar {
+    pub rate: f32,
+}
+
+impl WithdrawalStrategy for ConstantDollar {
+    fn annual_withdrawal(&self, saver: &Saver) -> Result<f32, WithdrawalError> {
+        match saver.annual_withdrawal {
+            Some(withdrawal) => checked(saver, withdrawal),
+            None => checked(saver, saver.liquid_assets() * self.rate),
+        }
+    }
+}
+
+// the same percent of whatever is left every year
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ConstantPercent {
+    pub rate: f32,
+}
+
+impl WithdrawalStrategy for ConstantPercent {
+    fn annual_withdrawal(&self, saver: &Saver) -> Result<f32, WithdrawalError> {
+        checked(saver, saver.liquid_assets() * self.rate)
+    }
+}
+
+// constant dollar spending that is cut when the withdrawal rate rises past the upper guardrail
+// and raised when it falls past the lower guardrail
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GuytonKlinger {
+    pub initial_rate: f32,
+    pub guardrail: f32,
+    pub adjustment: f32,
+}
+
+impl WithdrawalStrategy for GuytonKlinger {
+    fn annual_withdrawal(&self, saver: &Saver) -> Result<f32, WithdrawalError> {
+        let liquid = saver.liquid_assets();
+        let withdrawal = match saver.annual_withdrawal {
+            None => liquid * self.initial_rate,
+            Some(withdrawal)
+                if withdrawal > liquid * self.initial_rate * (1.0 + self.guardrail) =>
+            {
+                withdrawal * (1.0 - self.adjustment)
+            }
+            Some(withdrawal)
+                if withdrawal < liquid * self.initial_rate * (1.0 - self.guardrail) =>
+            {
+                withdrawal * (1.0 + self.adjustment)
+            }
+            Some(withdrawal) => withdrawal,
+        };
+        checked(saver, withdrawal)
+    }
+}
+
+// the payment that would spend savings down to nothing by the end age (of the younger member) at the expected real return
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Vpw {
+    pub real_return: f32,
+    pub end_age: u8,
+}
+
+impl WithdrawalStrategy for Vpw {
+    fn annual_withdrawal(&self, saver: &Saver) -> Result<f32, WithdrawalError> {
+        let years = Household::last_age(saver, self.end_age)
+            .saturating_sub(saver.current_age)
+            .max(1) as i32;
+        let withdrawal = if self.real_return == 0.0 {
+            saver.liquid_assets() / years as f32
+        } else {
+            saver.liquid_assets() * self.real_return / (1.0 - (1.0 + self.real_return).powi(-years))
+        };
+        checked(saver, withdrawal)
+    }
+}
+
+// savings divided by the irs distribution period for the age (years the household has left before the table starts)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RmdSpending;
+
+impl WithdrawalStrategy for RmdSpending {
+    fn annual_withdrawal(&self, saver: &Saver) -> Result<f32, WithdrawalError> {
+        let divisor = rmd_divisor(saver.current_age)
+            .max(Household::last_age(saver, DEATH as u8).saturating_sub(saver.current_age) as f32);
+        checked(saver, saver.liquid_assets() / divisor)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Strategy {
+    ConstantDollar(ConstantDollar),
+    ConstantPercent(ConstantPercent),
+    GuytonKlinger(GuytonKlinger),
+    Vpw(Vpw),
+    Rmd(RmdSpending),
+}
+
+impl WithdrawalStrategy for Strategy {
+    fn annual_withdrawal(&self, saver: &Saver) -> Result<f32, WithdrawalError> {
+        match self {
+            Strategy::ConstantDollar(strategy) => strategy.annual_withdrawal(saver),
+            Strategy::ConstantPercent(strategy) => strategy.annual_withdrawal(saver),
+            Strategy::GuytonKlinger(strategy) => strategy.annual_withdrawal(saver),
+            Strategy::Vpw(strategy) => strategy.annual_withdrawal(saver),
+            Strategy::Rmd(strategy) => strategy.annual_withdrawal(saver),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calculate::saver::tests::saver;
+
+    const GUARDRAILS: GuytonKlinger = GuytonKlinger {
+        initial_rate: STD_WITHDRAWAL_RATE,
+        guardrail: GUARDRAIL,
+        adjustment: GUARDRAIL_ADJUSTMENT,
+    };
+
+    // the fixture holds 100k of liquid savings next to its home equity
+    fn retiree(annual_withdrawal: Option<f32>) -> Saver<'static> {
+        Saver {
+            annual_withdrawal,
+            ..saver()
+        }
+    }
+
+    // within a dollar
+    fn assert_close(actual: f32, expected: f32) {
+        assert!(
+            (actual - expected).abs() < 1.0,
+            "{} != {}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn every_strategy_rejects_savings_that_are_not_a_number() {
+        let broke = Saver {
+            total_savings: f32::NAN,
+            ..saver()
+        };
+        let strategies = [
+            Strategy::ConstantDollar(ConstantDollar { rate: 0.04 }),
+            Strategy::ConstantPercent(ConstantPercent { rate: 0.04 }),
+            Strategy::GuytonKlinger(GUARDRAILS),
+            Strategy::Vpw(Vpw {
+                real_return: VPW_REAL_RETURN,
+                end_age: 95,
+            }),
+            Strategy::Rmd(RmdSpending),
+        ];
+        for strategy in strategies {
+            assert_eq!(
+                strategy.annual_withdrawal(&broke),
+                Err(WithdrawalError::NotANumber { age: 30 }),
+                "{:?}",
+                strategy
+            );
+        }
+    }
+
+    #[test]
+    fn constant_dollar_keeps_the_first_year_spending() {
+        let strategy = ConstantDollar { rate: 0.04 };
+        assert_close(strategy.annual_withdrawal(&retiree(None)).unwrap(), 4000.0);
+        assert_eq!(
+            strategy.annual_withdrawal(&retiree(Some(4200.0))),
+            Ok(4200.0)
+        );
+    }
+
+    #[test]
+    fn constant_percent_follows_the_savings() {
+        let strategy = ConstantPercent { rate: 0.05 };
+        assert_close(
+            strategy.annual_withdrawal(&retiree(Some(4200.0))).unwrap(),
+            5000.0,
+        );
+    }
+
+    #[test]
+    fn guardrails_cut_and_raise_the_withdrawal() {
+        // the guardrails sit at 4,800 and 3,200 on 100k of savings
+        assert_close(
+            GUARDRAILS.annual_withdrawal(&retiree(None)).unwrap(),
+            4000.0,
+        );
+        assert_close(
+            GUARDRAILS
+                .annual_withdrawal(&retiree(Some(6000.0)))
+                .unwrap(),
+            5400.0,
+        );
+        assert_close(
+            GUARDRAILS
+                .annual_withdrawal(&retiree(Some(3000.0)))
+                .unwrap(),
+            3300.0,
+        );
+        assert_eq!(
+            GUARDRAILS.annual_withdrawal(&retiree(Some(4500.0))),
+            Ok(4500.0)
+        );
+    }
+
+    #[test]
+    fn vpw_spends_savings_down_by_the_end_age() {
+        let even = Vpw {
+            real_return: 0.0,
+            end_age: 40,
+        };
+        assert_close(even.annual_withdrawal(&retiree(None)).unwrap(), 10000.0);
+        let last_year = Vpw {
+            real_return: VPW_REAL_RETURN,
+            end_age: 31,
+        };
+        assert_close(
+            last_year.annual_withdrawal(&retiree(None)).unwrap(),
+            104000.0,
+        );
+    }
+
+    #[test]
+    fn rmd_spending_divides_by_the_distribution_period() {
+        let old = Saver {
+            current_age: 90,
+            ..retiree(None)
+        };
+        assert_close(
+            RmdSpending.annual_withdrawal(&old).unwrap(),
+            100000.0 / 12.2,
+        );
+        // years left until 100 before the table starts
+        let young = Saver {
+            current_age: 60,
+            ..retiree(None)
+        };
+        assert_close(RmdSpending.annual_withdrawal(&young).unwrap(), 2500.0);
+    }
+}
@@ -1,18 +1,36 @@
-mod calculate;
-
-use crate::calculate::consts::*;
-use crate::calculate::rates::new_rates;
-use crate::calculate::saver::{Saver, SaverType};
+use rent_vs_own::calculate::accounts::WITHDRAWAL_ORDERS;
+use rent_vs_own::calculate::allocation::{GlidePath, Mix};
+use rent_vs_own::calculate::assets::{AssetModel, ALL_ASSETS};
+use rent_vs_own::calculate::consts::*;
+use rent_vs_own::calculate::events::LifeEvent;
+use rent_vs_own::calculate::history::{HISTORICAL_RETURNS, HISTORY_START_YEAR};
+use rent_vs_own::calculate::household::{Household, Partner};
+use rent_vs_own::calculate::owner::{Arm, Owner};
+use rent_vs_own::calculate::rates::{
+    new_seed, BlockBootstrap, Lognormal, Model, Normal, RatePath, RegimeSwitching, StudentT, Uniform,
+};
+use rent_vs_own::calculate::saver::{Saver, SaverType};
+use rent_vs_own::calculate::simulation::{
+    backtest, correlated_monte_carlo, monte_carlo, prepay_comparison, Bands, MONTE_CARLO_RUNS,
+};
+use rent_vs_own::calculate::tax::{FilingStatus, TaxConfig, Taxpayer};
+use rent_vs_own::calculate::wages::WageGrowth;
+use rent_vs_own::calculate::withdrawal::{
+    ConstantDollar, ConstantPercent, GuytonKlinger, RmdSpending, Strategy, Vpw, GUARDRAIL,
+    GUARDRAIL_ADJUSTMENT, STD_WITHDRAWAL_RATE, VPW_REAL_RETURN,
+};
 
 use leptos::*;
-use leptos_use::utils::Pausable;
-use leptos_use::*;
 use num_format::{Locale, ToFormattedString};
-use plotly::color::NamedColor;
-use plotly::common::{Anchor, DashType, Font, Line, Title};
+use plotly::color::{NamedColor, Rgba};
+use plotly::common::{Anchor, AxisSide, DashType, Fill, Font, Line, Marker, Mode, Position, Title};
 use plotly::layout::{Axis, Margin};
 use plotly::Plot;
 use plotly::Scatter;
+use std::time::Duration;
+
+// how long inputs have to stay put before the simulation reruns
+const SIMULATION_DELAY_MS: u64 = 300;
 
 #[derive(Clone, Debug)]
 pub struct OptionMeta {
@@ -20,7 +38,16 @@ pub struct OptionMeta {
     pub name: String,
     pub info: String,
     pub default_val: Opts,
-    pub optarr: &'static [Opts; 100],
+    pub optarr: &'static [Opts],
+}
+
+// a select over named choices, the value is the index of the chosen name
+#[derive(Clone, Debug)]
+pub struct ChoiceMeta {
+    pub name: String,
+    pub info: String,
+    pub default_val: u8,
+    pub choices: &'static [&'static str],
 }
 
 fn main() {
@@ -60,7 +87,6 @@ fn App() -> impl IntoView {
 
     let (expand_methodology, set_expand_methodology) = create_signal(false);
     let (expand_y_axis_settings, set_expand_y_axis_settings) = create_signal(false);
-    let (pause_resume, set_pause_resume) = create_signal(false);
     let (find_equivelent_rent, set_find_equivelent_rent) = create_signal(false);
 
     let default_age = 30;
@@ -97,11 +123,60 @@ fn App() -> impl IntoView {
     let monthly_income_opts = move || OptionMeta {
         numtype: OptType::Float,
         name: "Monthly Income".to_string(),
-        info: "this is your current monthly income".to_string(),
+        info: "this is your current monthly income (before taxes when a filing status is chosen)".to_string(),
         default_val: Opts::Float(default_monthly_income),
         optarr: &INCEXP_RANGE,
     };
 
+    let default_wage_profile = 0;
+    let (wage_profile, set_wage_profile) = create_signal(default_wage_profile);
+    let wage_profile_opts = move || ChoiceMeta {
+        name: "Real Wage Growth Profile".to_string(),
+        info: "how your income grows on top of inflation over your career".to_string(),
+        default_val: default_wage_profile,
+        choices: &["Flat", "Linear", "Hump-Shaped"],
+    };
+
+    let default_wage_growth = 0.02;
+    let (wage_growth, set_wage_growth) = create_signal(Opts::Float(default_wage_growth));
+    let wage_growth_opts = move || OptionMeta {
+        numtype: OptType::Float,
+        name: "Real Wage Growth".to_string(),
+        info: "this is your yearly raise beyond inflation (early in your career for hump-shaped)".to_string(),
+        default_val: Opts::Float(default_wage_growth),
+        optarr: &APPRECIATION_RATES,
+    };
+
+    let default_peak_earnings_age = 50;
+    let (peak_earnings_age, set_peak_earnings_age) = create_signal(Opts::Int(default_peak_earnings_age));
+    let peak_earnings_age_opts = move || OptionMeta {
+        numtype: OptType::Int,
+        name: "Peak Earnings Age".to_string(),
+        info: "real raises shrink to nothing by this age and turn into real pay cuts after".to_string(),
+        default_val: Opts::Int(default_peak_earnings_age),
+        optarr: &AGE_RANGE,
+    };
+
+    let default_wage_volatility = 0.0;
+    let (wage_volatility, set_wage_volatility) = create_signal(Opts::Float(default_wage_volatility));
+    let wage_volatility_opts = move || OptionMeta {
+        numtype: OptType::Float,
+        name: "Wage Volatility".to_string(),
+        info: "this is the standard deviation of yearly income shocks on top of the growth profile".to_string(),
+        default_val: Opts::Float(default_wage_volatility),
+        optarr: &WAGE_VOLATILITY_RATES,
+    };
+
+    let default_wage_market_correlation = 0.3;
+    let (wage_market_correlation, set_wage_market_correlation) = create_signal(Opts::Float(default_wage_market_correlation));
+    let wage_market_correlation_opts = move || OptionMeta {
+        numtype: OptType::Float,
+        name: "Wage Market Correlation".to_string(),
+        info: "this is how closely income shocks follow market returns".to_string(),
+        default_val: Opts::Float(default_wage_market_correlation),
+        optarr: &CORRELATION_RANGE,
+    };
+
     let default_monthly_expenses = 5000.0;
     let (monthly_expenses, set_monthly_expenses) = create_signal(Opts::Float(default_monthly_expenses));
     let monthly_expenses_opts = move || OptionMeta {
@@ -138,6 +213,120 @@ fn App() -> impl IntoView {
         }
     };
 
+    let default_home_appreciation = 0.0;
+    let (home_appreciation, set_home_appreciation) = create_signal(Opts::Float(default_home_appreciation));
+    let home_appreciation_opts = move || {
+        OptionMeta {
+            numtype: OptType::Float,
+            name: "Home Appreciation".to_string(),
+            info: "this is the annual appreciation of your home above inflation (0% tracks inflation)".to_string(),
+            default_val: Opts::Float(default_home_appreciation),
+            optarr: &APPRECIATION_RATES,
+        }
+    };
+
+    let default_property_tax = 0.01;
+    let (property_tax, set_property_tax) = create_signal(Opts::Float(default_property_tax));
+    let property_tax_opts = move || OptionMeta {
+        numtype: OptType::Float,
+        name: "Property Tax".to_string(),
+        info: "this is the annual property tax rate on your home value".to_string(),
+        default_val: Opts::Float(default_property_tax),
+        optarr: &HOME_EXPENSE_RATES,
+    };
+
+    let default_maintenance = 0.01;
+    let (maintenance, set_maintenance) = create_signal(Opts::Float(default_maintenance));
+    let maintenance_opts = move || OptionMeta {
+        numtype: OptType::Float,
+        name: "Maintenance".to_string(),
+        info: "this is the annual upkeep of your home as a percent of its value".to_string(),
+        default_val: Opts::Float(default_maintenance),
+        optarr: &HOME_EXPENSE_RATES,
+    };
+
+    let default_hoa = 0.0;
+    let (hoa, set_hoa) = create_signal(Opts::Float(default_hoa));
+    let hoa_opts = move || OptionMeta {
+        numtype: OptType::Float,
+        name: "HOA Dues".to_string(),
+        info: "this is your monthly home owners association dues".to_string(),
+        default_val: Opts::Float(default_hoa),
+        optarr: &HOME_COST_RANGE,
+    };
+
+    let default_insurance = 150.0;
+    let (insurance, set_insurance) = create_signal(Opts::Float(default_insurance));
+    let insurance_opts = move || OptionMeta {
+        numtype: OptType::Float,
+        name: "Home Insurance".to_string(),
+        info: "this is your monthly home owner's insurance premium".to_string(),
+        default_val: Opts::Float(default_insurance),
+        optarr: &HOME_COST_RANGE,
+    };
+
+    let default_closing_costs = 0.03;
+    let (closing_costs, set_closing_costs) = create_signal(Opts::Float(default_closing_costs));
+    let closing_costs_opts = move || OptionMeta {
+        numtype: OptType::Float,
+        name: "Closing Costs".to_string(),
+        info: "this is the buyer's closing costs and points as a percent of the price of the home bought later (the owner already owns their home)".to_string(),
+        default_val: Opts::Float(default_closing_costs),
+        optarr: &HOME_EXPENSE_RATES,
+    };
+
+    let default_selling_costs = 0.06;
+    let (selling_costs, set_selling_costs) = create_signal(Opts::Float(default_selling_costs));
+    let selling_costs_opts = move || OptionMeta {
+        numtype: OptType::Float,
+        name: "Selling Costs".to_string(),
+        info: "this is the realtor and transfer fees as a percent of the home value when it is sold".to_string(),
+        default_val: Opts::Float(default_selling_costs),
+        optarr: &HOME_EXPENSE_RATES,
+    };
+
+    let default_sale_age = 0;
+    let (sale_age, set_sale_age) = create_signal(Opts::Int(default_sale_age));
+    let sale_age_opts = move || OptionMeta {
+        numtype: OptType::Int,
+        name: "Sale Age".to_string(),
+        info: "this is the age you sell the home and go back to paying rent (0 keeps the home until the end)".to_string(),
+        default_val: Opts::Int(default_sale_age),
+        optarr: &AGE_RANGE,
+    };
+
+    let default_purchase_age = default_age + 3;
+    let (purchase_age, set_purchase_age) = create_signal(Opts::Int(default_purchase_age));
+    let purchase_age_opts = move || OptionMeta {
+        numtype: OptType::Int,
+        name: "Buy Later Age".to_string(),
+        info: "this is the age the buy later saver stops renting and buys a home, later if savings can't cover the down payment and closing costs yet".to_string(),
+        default_val: Opts::Int(default_purchase_age),
+        optarr: &AGE_RANGE,
+    };
+
+    let default_purchase_home_value = 500000.0;
+    let (purchase_home_value, set_purchase_home_value) = create_signal(Opts::Float(default_purchase_home_value));
+    let purchase_home_value_opts = move || {
+        OptionMeta {
+            numtype: OptType::Float,
+            name: "Buy Later Home Value".to_string(),
+            info: "this is the value of the home bought later in today's dollars (it grows with home prices until purchase)".to_string(),
+            default_val: Opts::Float(default_purchase_home_value),
+            optarr: &NETWORTH_RANGE,
+        }
+    };
+
+    let default_down_payment = 0.2;
+    let (down_payment, set_down_payment) = create_signal(Opts::Float(default_down_payment));
+    let down_payment_opts = move || OptionMeta {
+        numtype: OptType::Float,
+        name: "Buy Later Down Payment".to_string(),
+        info: "this is the down payment on the home bought later as a percent of its price".to_string(),
+        default_val: Opts::Float(default_down_payment),
+        optarr: &DOWN_PAYMENT_RATES,
+    };
+
     let default_mortgage = 400000.0;
     let (mortgage, set_mortgage) = create_signal(Opts::Float(default_mortgage));
     let mortgage_opts = move || OptionMeta {
@@ -168,13 +357,522 @@ fn App() -> impl IntoView {
         optarr: &AGE_RANGE,
     };
 
+    let default_refinance_age = 0;
+    let (refinance_age, set_refinance_age) = create_signal(Opts::Int(default_refinance_age));
+    let refinance_age_opts = move || OptionMeta {
+        numtype: OptType::Int,
+        name: "Refinance Age".to_string(),
+        info: "this is the age you refinance at the market mortgage rate (0 never refinances on a schedule)".to_string(),
+        default_val: Opts::Int(default_refinance_age),
+        optarr: &AGE_RANGE,
+    };
+
+    let default_refinance_rate_drop = 0.0;
+    let (refinance_rate_drop, set_refinance_rate_drop) = create_signal(Opts::Float(default_refinance_rate_drop));
+    let refinance_rate_drop_opts = move || OptionMeta {
+        numtype: OptType::Float,
+        name: "Refinance Rate Drop".to_string(),
+        info: "refinance whenever the market mortgage rate falls this far below your rate (0% never)".to_string(),
+        default_val: Opts::Float(default_refinance_rate_drop),
+        optarr: &HOME_EXPENSE_RATES,
+    };
+
+    let default_refinance_term = 30;
+    let (refinance_term, set_refinance_term) = create_signal(Opts::Int(default_refinance_term));
+    let refinance_term_opts = move || OptionMeta {
+        numtype: OptType::Int,
+        name: "Refinance Term".to_string(),
+        info: "duration of the refinanced mortgage in years".to_string(),
+        default_val: Opts::Int(default_refinance_term),
+        optarr: &AGE_RANGE,
+    };
+
+    let default_refinance_costs = 0.02;
+    let (refinance_costs, set_refinance_costs) = create_signal(Opts::Float(default_refinance_costs));
+    let refinance_costs_opts = move || OptionMeta {
+        numtype: OptType::Float,
+        name: "Refinance Costs".to_string(),
+        info: "this is the closing costs of a refinance as a percent of the remaining mortgage".to_string(),
+        default_val: Opts::Float(default_refinance_costs),
+        optarr: &HOME_EXPENSE_RATES,
+    };
+
+    let default_arm_fixed_years = 0;
+    let (arm_fixed_years, set_arm_fixed_years) = create_signal(Opts::Int(default_arm_fixed_years));
+    let arm_fixed_years_opts = move || OptionMeta {
+        numtype: OptType::Int,
+        name: "ARM Fixed Years".to_string(),
+        info: "years before an adjustable rate starts resetting every year, e.g. 5, 7 or 10 (0 is a fixed rate mortgage)".to_string(),
+        default_val: Opts::Int(default_arm_fixed_years),
+        optarr: &AGE_RANGE,
+    };
+
+    let default_arm_margin = 0.025;
+    let (arm_margin, set_arm_margin) = create_signal(Opts::Float(default_arm_margin));
+    let arm_margin_opts = move || OptionMeta {
+        numtype: OptType::Float,
+        name: "ARM Margin".to_string(),
+        info: "this is added to the index rate at each reset".to_string(),
+        default_val: Opts::Float(default_arm_margin),
+        optarr: &ARM_SPREAD_RATES,
+    };
+
+    let default_arm_periodic_cap = 0.02;
+    let (arm_periodic_cap, set_arm_periodic_cap) = create_signal(Opts::Float(default_arm_periodic_cap));
+    let arm_periodic_cap_opts = move || OptionMeta {
+        numtype: OptType::Float,
+        name: "ARM Periodic Cap".to_string(),
+        info: "this is the most the rate can move at a single reset".to_string(),
+        default_val: Opts::Float(default_arm_periodic_cap),
+        optarr: &ARM_SPREAD_RATES,
+    };
+
+    let default_arm_lifetime_cap = 0.05;
+    let (arm_lifetime_cap, set_arm_lifetime_cap) = create_signal(Opts::Float(default_arm_lifetime_cap));
+    let arm_lifetime_cap_opts = move || OptionMeta {
+        numtype: OptType::Float,
+        name: "ARM Lifetime Cap".to_string(),
+        info: "this is the most the rate can ever rise above the initial mortgage rate".to_string(),
+        default_val: Opts::Float(default_arm_lifetime_cap),
+        optarr: &ARM_SPREAD_RATES,
+    };
+
+    let default_extra_monthly_principal = 0.0;
+    let (extra_monthly_principal, set_extra_monthly_principal) = create_signal(Opts::Float(default_extra_monthly_principal));
+    let extra_monthly_principal_opts = move || OptionMeta {
+        numtype: OptType::Float,
+        name: "Extra Monthly Principal".to_string(),
+        info: "this is paid toward the mortgage principal every month on top of the regular payment".to_string(),
+        default_val: Opts::Float(default_extra_monthly_principal),
+        optarr: &INCEXP_RANGE,
+    };
+
+    let default_extra_annual_principal = 0.0;
+    let (extra_annual_principal, set_extra_annual_principal) = create_signal(Opts::Float(default_extra_annual_principal));
+    let extra_annual_principal_opts = move || OptionMeta {
+        numtype: OptType::Float,
+        name: "Extra Annual Principal".to_string(),
+        info: "this is paid toward the mortgage principal once a year on top of the regular payments".to_string(),
+        default_val: Opts::Float(default_extra_annual_principal),
+        optarr: &INCEXP_RANGE,
+    };
+
+    let default_pmi_rate = 0.005;
+    let (pmi_rate, set_pmi_rate) = create_signal(Opts::Float(default_pmi_rate));
+    let pmi_rate_opts = move || OptionMeta {
+        numtype: OptType::Float,
+        name: "PMI Rate".to_string(),
+        info: "this is the annual private mortgage insurance rate on the mortgage".to_string(),
+        default_val: Opts::Float(default_pmi_rate),
+        optarr: &HOME_EXPENSE_RATES,
+    };
+
+    let default_pmi_ltv_cutoff = 0.78;
+    let (pmi_ltv_cutoff, set_pmi_ltv_cutoff) = create_signal(Opts::Float(default_pmi_ltv_cutoff));
+    let pmi_ltv_cutoff_opts = move || OptionMeta {
+        numtype: OptType::Float,
+        name: "PMI LTV Cutoff".to_string(),
+        info: "private mortgage insurance is paid until the mortgage falls to this percent of the home value".to_string(),
+        default_val: Opts::Float(default_pmi_ltv_cutoff),
+        optarr: &DOWN_PAYMENT_RATES,
+    };
+
+    let default_filing_status = 0;
+    let (filing_status, set_filing_status) = create_signal(default_filing_status);
+    let filing_status_opts = move || ChoiceMeta {
+        name: "Filing Status".to_string(),
+        info: "income taxes are figured with this filing status, none treats monthly income as take home pay".to_string(),
+        default_val: default_filing_status,
+        choices: &["None", "Single", "Married Filing Jointly"],
+    };
+
+    let default_state_tax = 0.05;
+    let (state_tax, set_state_tax) = create_signal(Opts::Float(default_state_tax));
+    let state_tax_opts = move || OptionMeta {
+        numtype: OptType::Float,
+        name: "State Income Tax".to_string(),
+        info: "this is the flat state income tax rate on your income".to_string(),
+        default_val: Opts::Float(default_state_tax),
+        optarr: &STATE_TAX_RATES,
+    };
+
+    let default_home_sale_exclusion = 250000.0;
+    let (home_sale_exclusion, set_home_sale_exclusion) = create_signal(Opts::Float(default_home_sale_exclusion));
+    let home_sale_exclusion_opts = move || OptionMeta {
+        numtype: OptType::Float,
+        name: "Home Sale Exclusion".to_string(),
+        info: "this much of the gain on selling your home is tax free (250,000 single, 500,000 married)".to_string(),
+        default_val: Opts::Float(default_home_sale_exclusion),
+        optarr: &NETWORTH_RANGE,
+    };
+
+    let default_pre_tax_savings = 0.0;
+    let (pre_tax_savings, set_pre_tax_savings) = create_signal(Opts::Float(default_pre_tax_savings));
+    let pre_tax_savings_opts = move || OptionMeta {
+        numtype: OptType::Float,
+        name: "Pre-Tax Savings".to_string(),
+        info: "this is how much of your net worth is in 401k and traditional ira accounts".to_string(),
+        default_val: Opts::Float(default_pre_tax_savings),
+        optarr: &NETWORTH_RANGE,
+    };
+
+    let default_roth_savings = 0.0;
+    let (roth_savings, set_roth_savings) = create_signal(Opts::Float(default_roth_savings));
+    let roth_savings_opts = move || OptionMeta {
+        numtype: OptType::Float,
+        name: "Roth Savings".to_string(),
+        info: "this is how much of your net worth is in roth accounts".to_string(),
+        default_val: Opts::Float(default_roth_savings),
+        optarr: &NETWORTH_RANGE,
+    };
+
+    let default_pre_tax_contribution = 0.0;
+    let (pre_tax_contribution, set_pre_tax_contribution) = create_signal(Opts::Float(default_pre_tax_contribution));
+    let pre_tax_contribution_opts = move || OptionMeta {
+        numtype: OptType::Float,
+        name: "Pre-Tax Contribution".to_string(),
+        info: "this percent of your monthly income goes to your 401k before taxes, up to the annual limit".to_string(),
+        default_val: Opts::Float(default_pre_tax_contribution),
+        optarr: &DOWN_PAYMENT_RATES,
+    };
+
+    let default_employer_match = 0.0;
+    let (employer_match, set_employer_match) = create_signal(Opts::Float(default_employer_match));
+    let employer_match_opts = move || OptionMeta {
+        numtype: OptType::Float,
+        name: "Employer Match".to_string(),
+        info: "your employer matches your pre-tax contributions up to this percent of your income".to_string(),
+        default_val: Opts::Float(default_employer_match),
+        optarr: &HOME_EXPENSE_RATES,
+    };
+
+    let default_roth_contribution = 0.0;
+    let (roth_contribution, set_roth_contribution) = create_signal(Opts::Float(default_roth_contribution));
+    let roth_contribution_opts = move || OptionMeta {
+        numtype: OptType::Float,
+        name: "Roth Contribution".to_string(),
+        info: "this percent of your monthly income goes to a roth ira after taxes, up to the annual limit".to_string(),
+        default_val: Opts::Float(default_roth_contribution),
+        optarr: &DOWN_PAYMENT_RATES,
+    };
+
+    let default_rmd_age = 73;
+    let (rmd_age, set_rmd_age) = create_signal(Opts::Int(default_rmd_age));
+    let rmd_age_opts = move || OptionMeta {
+        numtype: OptType::Int,
+        name: "RMD Age".to_string(),
+        info: "required minimum distributions are taken from pre-tax accounts every year starting at this age".to_string(),
+        default_val: Opts::Int(default_rmd_age),
+        optarr: &AGE_RANGE,
+    };
+
+    let default_withdrawal_order = 0;
+    let (withdrawal_order, set_withdrawal_order) = create_signal(default_withdrawal_order);
+    let withdrawal_order_opts = move || ChoiceMeta {
+        name: "Withdrawal Order".to_string(),
+        info: "when spending is more than income your accounts are drawn down in this order".to_string(),
+        default_val: default_withdrawal_order,
+        choices: &[
+            "Taxable, Pre-Tax, Roth",
+            "Taxable, Roth, Pre-Tax",
+            "Pre-Tax, Taxable, Roth",
+            "Pre-Tax, Roth, Taxable",
+            "Roth, Taxable, Pre-Tax",
+            "Roth, Pre-Tax, Taxable",
+        ],
+    };
+
+    let default_withdrawal_strategy = 1;
+    let (withdrawal_strategy, set_withdrawal_strategy) = create_signal(default_withdrawal_strategy);
+    let withdrawal_strategy_opts = move || ChoiceMeta {
+        name: "Withdrawal Strategy".to_string(),
+        info: "this decides how much you spend out of savings each year of retirement".to_string(),
+        default_val: default_withdrawal_strategy,
+        choices: &[
+            "Constant Dollar",
+            "Constant Percent",
+            "Guyton-Klinger Guardrails",
+            "Variable Percentage (VPW)",
+            "RMD Based",
+        ],
+    };
+
+    let default_withdrawal_rate = STD_WITHDRAWAL_RATE;
+    let (withdrawal_rate, set_withdrawal_rate) = create_signal(Opts::Float(default_withdrawal_rate));
+    let withdrawal_rate_opts = move || OptionMeta {
+        numtype: OptType::Float,
+        name: "Withdrawal Rate".to_string(),
+        info: "this is the percent of savings withdrawn (the first year for constant dollar and guardrails)".to_string(),
+        default_val: Opts::Float(default_withdrawal_rate),
+        optarr: &WITHDRAWAL_RATES,
+    };
+
+    let default_social_security_age = 67;
+    let (social_security_age, set_social_security_age) = create_signal(Opts::Int(default_social_security_age));
+    let social_security_age_opts = move || OptionMeta {
+        numtype: OptType::Int,
+        name: "Social Security Age".to_string(),
+        info: "this is the age you start claiming social security".to_string(),
+        default_val: Opts::Int(default_social_security_age),
+        optarr: &AGE_RANGE,
+    };
+
+    let default_social_security = 2000.0;
+    let (social_security, set_social_security) = create_signal(Opts::Float(default_social_security));
+    let social_security_opts = move || OptionMeta {
+        numtype: OptType::Float,
+        name: "Social Security Benefit".to_string(),
+        info: "this is your monthly social security benefit at the claiming age (in today's dollars)".to_string(),
+        default_val: Opts::Float(default_social_security),
+        optarr: &INCEXP_RANGE,
+    };
+
+    let default_pension = 0.0;
+    let (pension, set_pension) = create_signal(Opts::Float(default_pension));
+    let pension_opts = move || OptionMeta {
+        numtype: OptType::Float,
+        name: "Pension".to_string(),
+        info: "this is your monthly pension when it starts (in today's dollars)".to_string(),
+        default_val: Opts::Float(default_pension),
+        optarr: &INCEXP_RANGE,
+    };
+
+    let default_pension_age = 65;
+    let (pension_age, set_pension_age) = create_signal(Opts::Int(default_pension_age));
+    let pension_age_opts = move || OptionMeta {
+        numtype: OptType::Int,
+        name: "Pension Age".to_string(),
+        info: "this is the age your pension starts paying".to_string(),
+        default_val: Opts::Int(default_pension_age),
+        optarr: &AGE_RANGE,
+    };
+
+    let default_pension_cola = 0;
+    let (pension_cola, set_pension_cola) = create_signal(default_pension_cola);
+    let pension_cola_opts = move || ChoiceMeta {
+        name: "Pension COLA".to_string(),
+        info: "a cost of living adjustment keeps the pension growing with inflation after it starts".to_string(),
+        default_val: default_pension_cola,
+        choices: &["No", "Yes"],
+    };
+
+    let default_has_partner = 0;
+    let (has_partner, set_has_partner) = create_signal(default_has_partner);
+    let has_partner_opts = move || ChoiceMeta {
+        name: "Partner".to_string(),
+        info: "a partner with their own age, income, retirement and benefits shares your savings and home".to_string(),
+        default_val: default_has_partner,
+        choices: &["No", "Yes"],
+    };
+
+    let default_partner_age = default_age;
+    let (partner_age, set_partner_age) = create_signal(Opts::Int(default_partner_age));
+    let partner_age_opts = move || OptionMeta {
+        numtype: OptType::Int,
+        name: "Partner Age".to_string(),
+        info: "this is your partner's current age".to_string(),
+        default_val: Opts::Int(default_partner_age),
+        optarr: &AGE_RANGE,
+    };
+
+    let default_partner_retirement_age = default_retirement_age;
+    let (partner_retirement_age, set_partner_retirement_age) = create_signal(Opts::Int(default_partner_retirement_age));
+    let partner_retirement_age_opts = move || OptionMeta {
+        numtype: OptType::Int,
+        name: "Partner Retirement Age".to_string(),
+        info: "this is the age your partner wants to retire at".to_string(),
+        default_val: Opts::Int(default_partner_retirement_age),
+        optarr: &AGE_RANGE,
+    };
+
+    let default_partner_income = 4000.0;
+    let (partner_income, set_partner_income) = create_signal(Opts::Float(default_partner_income));
+    let partner_income_opts = move || OptionMeta {
+        numtype: OptType::Float,
+        name: "Partner Monthly Income".to_string(),
+        info: "this is your partner's current monthly income (before taxes when a filing status is chosen)".to_string(),
+        default_val: Opts::Float(default_partner_income),
+        optarr: &INCEXP_RANGE,
+    };
+
+    let default_partner_social_security_age = default_social_security_age;
+    let (partner_social_security_age, set_partner_social_security_age) = create_signal(Opts::Int(default_partner_social_security_age));
+    let partner_social_security_age_opts = move || OptionMeta {
+        numtype: OptType::Int,
+        name: "Partner Social Security Age".to_string(),
+        info: "this is the age your partner starts claiming social security".to_string(),
+        default_val: Opts::Int(default_partner_social_security_age),
+        optarr: &AGE_RANGE,
+    };
+
+    let default_partner_social_security = 1500.0;
+    let (partner_social_security, set_partner_social_security) = create_signal(Opts::Float(default_partner_social_security));
+    let partner_social_security_opts = move || OptionMeta {
+        numtype: OptType::Float,
+        name: "Partner Social Security Benefit".to_string(),
+        info: "this is your partner's monthly social security benefit at their claiming age (in today's dollars)".to_string(),
+        default_val: Opts::Float(default_partner_social_security),
+        optarr: &INCEXP_RANGE,
+    };
+
+    let default_partner_pension = 0.0;
+    let (partner_pension, set_partner_pension) = create_signal(Opts::Float(default_partner_pension));
+    let partner_pension_opts = move || OptionMeta {
+        numtype: OptType::Float,
+        name: "Partner Pension".to_string(),
+        info: "this is your partner's monthly pension when it starts (in today's dollars)".to_string(),
+        default_val: Opts::Float(default_partner_pension),
+        optarr: &INCEXP_RANGE,
+    };
+
+    let default_partner_pension_age = default_pension_age;
+    let (partner_pension_age, set_partner_pension_age) = create_signal(Opts::Int(default_partner_pension_age));
+    let partner_pension_age_opts = move || OptionMeta {
+        numtype: OptType::Int,
+        name: "Partner Pension Age".to_string(),
+        info: "this is the age your partner's pension starts paying".to_string(),
+        default_val: Opts::Int(default_partner_pension_age),
+        optarr: &AGE_RANGE,
+    };
+
+    let default_partner_pension_cola = 0;
+    let (partner_pension_cola, set_partner_pension_cola) = create_signal(default_partner_pension_cola);
+    let partner_pension_cola_opts = move || ChoiceMeta {
+        name: "Partner Pension COLA".to_string(),
+        info: "a cost of living adjustment keeps the pension growing with inflation after it starts".to_string(),
+        default_val: default_partner_pension_cola,
+        choices: &["No", "Yes"],
+    };
+
+    let default_retirement_expense_change = -0.20;
+    let (retirement_expense_change, set_retirement_expense_change) = create_signal(Opts::Float(default_retirement_expense_change));
+    let retirement_expense_change_opts = move || OptionMeta {
+        numtype: OptType::Float,
+        name: "Retirement Expense Change".to_string(),
+        info: "your monthly expenses change by this percent when you retire".to_string(),
+        default_val: Opts::Float(default_retirement_expense_change),
+        optarr: &EXPENSE_CHANGE_RATES,
+    };
+
+    let default_healthcare = 500.0;
+    let (healthcare, set_healthcare) = create_signal(Opts::Float(default_healthcare));
+    let healthcare_opts = move || OptionMeta {
+        numtype: OptType::Float,
+        name: "Retirement Healthcare".to_string(),
+        info: "this is your monthly out of pocket healthcare cost in retirement (in today's dollars)".to_string(),
+        default_val: Opts::Float(default_healthcare),
+        optarr: &HOME_COST_RANGE,
+    };
+
+    let default_healthcare_inflation = 0.02;
+    let (healthcare_inflation, set_healthcare_inflation) = create_signal(Opts::Float(default_healthcare_inflation));
+    let healthcare_inflation_opts = move || OptionMeta {
+        numtype: OptType::Float,
+        name: "Healthcare Inflation".to_string(),
+        info: "healthcare costs grow this much faster than inflation every year".to_string(),
+        default_val: Opts::Float(default_healthcare_inflation),
+        optarr: &HOME_EXPENSE_RATES,
+    };
+
+    let default_spending_smile = 0;
+    let (spending_smile, set_spending_smile) = create_signal(default_spending_smile);
+    let spending_smile_opts = move || ChoiceMeta {
+        name: "Spending Smile".to_string(),
+        info: "real spending falls through early retirement and rises again late in life".to_string(),
+        default_val: default_spending_smile,
+        choices: &["No", "Yes"],
+    };
+
+    let (life_events, set_life_events) = create_signal(Vec::<LifeEvent>::new());
+
+    let default_event_type = 0;
+    let (event_type, set_event_type) = create_signal(default_event_type);
+    let event_type_opts = move || ChoiceMeta {
+        name: "Event".to_string(),
+        info: "pick an event and its ages, then add it to the timeline".to_string(),
+        default_val: default_event_type,
+        choices: &["Windfall", "Income Change", "Recurring Expense", "Unemployment"],
+    };
+
+    let default_event_start_age = default_age + 5;
+    let (event_start_age, set_event_start_age) = create_signal(Opts::Int(default_event_start_age));
+    let event_start_age_opts = move || OptionMeta {
+        numtype: OptType::Int,
+        name: "Event Age".to_string(),
+        info: "this is the age the event happens or starts".to_string(),
+        default_val: Opts::Int(default_event_start_age),
+        optarr: &AGE_RANGE,
+    };
+
+    let default_event_end_age = default_event_start_age + 2;
+    let (event_end_age, set_event_end_age) = create_signal(Opts::Int(default_event_end_age));
+    let event_end_age_opts = move || OptionMeta {
+        numtype: OptType::Int,
+        name: "Event End Age".to_string(),
+        info: "the event lasts until this age".to_string(),
+        default_val: Opts::Int(default_event_end_age),
+        optarr: &AGE_RANGE,
+    };
+
+    let default_event_amount = 50000.0;
+    let (event_amount, set_event_amount) = create_signal(Opts::Float(default_event_amount));
+    let event_amount_opts = move || OptionMeta {
+        numtype: OptType::Float,
+        name: "Windfall Amount".to_string(),
+        info: "this one off amount is added to your savings (in today's dollars)".to_string(),
+        default_val: Opts::Float(default_event_amount),
+        optarr: &NETWORTH_RANGE,
+    };
+
+    let default_event_change = 0.10;
+    let (event_change, set_event_change) = create_signal(Opts::Float(default_event_change));
+    let event_change_opts = move || OptionMeta {
+        numtype: OptType::Float,
+        name: "Income Change".to_string(),
+        info: "your income changes by this percent while the event lasts".to_string(),
+        default_val: Opts::Float(default_event_change),
+        optarr: &EXPENSE_CHANGE_RATES,
+    };
+
+    let default_event_monthly = 1000.0;
+    let (event_monthly, set_event_monthly) = create_signal(Opts::Float(default_event_monthly));
+    let event_monthly_opts = move || OptionMeta {
+        numtype: OptType::Float,
+        name: "Monthly Expense".to_string(),
+        info: "this is the recurring monthly expense while the event lasts, like childcare or tuition (in today's dollars)".to_string(),
+        default_val: Opts::Float(default_event_monthly),
+        optarr: &INCEXP_RANGE,
+    };
+
+    let new_event = move || {
+        let start_age = event_start_age.get_untracked().get_int();
+        let end_age = event_end_age.get_untracked().get_int();
+        match event_type.get_untracked() {
+            0 => LifeEvent::Windfall {
+                age: start_age,
+                amount: event_amount.get_untracked().get_float(),
+            },
+            1 => LifeEvent::IncomeChange {
+                start_age,
+                end_age,
+                change: event_change.get_untracked().get_float(),
+            },
+            2 => LifeEvent::Expense {
+                start_age,
+                end_age,
+                monthly: event_monthly.get_untracked().get_float(),
+            },
+            _ => LifeEvent::Unemployment { start_age, end_age },
+        }
+    };
+
     let default_min_retirement_income = 2000.0;
     let (min_retirement_income, set_min_retirement_income) = create_signal(Opts::Float(default_min_retirement_income));
     let min_retirement_income_opts = move || {
         OptionMeta {
             numtype: OptType::Float,
             name: "Min Monthly Retirement Income".to_string(),
-            info: "this is the minimum amount of monthly income you want to have in retirement (in today's dollars)".to_string(),
+            info: "this is the minimum amount of monthly income you want to have in retirement, benefits included (in today's dollars)".to_string(),
             default_val: Opts::Float(default_min_retirement_income),
             optarr: &INCEXP_RANGE,
         }
@@ -185,78 +883,401 @@ fn App() -> impl IntoView {
         OptionMeta {
             numtype: OptType::Float,
             name: "Max Monthly Retirement Income".to_string(),
-            info: "this is the maximum amount of monthly income you want to have in retirement (in today's dollars)".to_string(),
+            info: "this is the maximum amount of monthly income you want to have in retirement, benefits included (in today's dollars)".to_string(),
             default_val: Opts::Float(default_max_retirement_income),
             optarr: &INCEXP_RANGE,
         }
     };
 
-    let (inflation_rates, set_inflation_rates) = create_signal(vec![Opts::Float(0.0); 100]);
-    let (interest_rates, set_interest_rates) = create_signal(vec![Opts::Float(0.0); 100]);
+    let default_rate_paths = 0;
+    let (rate_paths, set_rate_paths) = create_signal(default_rate_paths);
+    let rate_paths_opts = move || ChoiceMeta {
+        name: "Rate Paths".to_string(),
+        info: "simulate random rates, replay history from every start year like cFIREsim (with synthetic mortgage rates of inflation plus 3%), or draw correlated asset paths".to_string(),
+        default_val: default_rate_paths,
+        choices: &["Simulated", "Historical", "Correlated Assets"],
+    };
+
+    // means, standard deviations and correlations of the correlated asset paths
+    let (asset_model, set_asset_model) = create_signal(AssetModel::default());
+
+    let default_return_model = 0;
+    let (return_model, set_return_model) = create_signal(default_return_model);
+    let return_model_opts = move || ChoiceMeta {
+        name: "Return Model".to_string(),
+        info: "how simulated yearly stock returns are drawn".to_string(),
+        default_val: default_return_model,
+        choices: &["Uniform", "Normal", "Lognormal", "Student-t", "Block Bootstrap", "Regime Switching"],
+    };
+
+    let default_mean_return = 0.10;
+    let (mean_return, set_mean_return) = create_signal(Opts::Float(default_mean_return));
+    let mean_return_opts = move || OptionMeta {
+        numtype: OptType::Float,
+        name: "Mean Return".to_string(),
+        info: "this is the expected yearly stock return (in a bull market when regime switching)".to_string(),
+        default_val: Opts::Float(default_mean_return),
+        optarr: &RETURN_RATES,
+    };
+
+    let default_return_std_dev = 0.17;
+    let (return_std_dev, set_return_std_dev) = create_signal(Opts::Float(default_return_std_dev));
+    let return_std_dev_opts = move || OptionMeta {
+        numtype: OptType::Float,
+        name: "Return Std Dev".to_string(),
+        info: "this is the standard deviation of yearly stock returns".to_string(),
+        default_val: Opts::Float(default_return_std_dev),
+        optarr: &RETURN_STD_DEV_RATES,
+    };
+
+    let default_degrees_of_freedom = 5;
+    let (degrees_of_freedom, set_degrees_of_freedom) = create_signal(Opts::Int(default_degrees_of_freedom));
+    let degrees_of_freedom_opts = move || OptionMeta {
+        numtype: OptType::Int,
+        name: "Degrees Of Freedom".to_string(),
+        info: "fewer degrees of freedom make crashes and booms more likely (at least 3)".to_string(),
+        default_val: Opts::Int(default_degrees_of_freedom),
+        optarr: &AGE_RANGE,
+    };
+
+    let default_block_years = 5;
+    let (block_years, set_block_years) = create_signal(Opts::Int(default_block_years));
+    let block_years_opts = move || OptionMeta {
+        numtype: OptType::Int,
+        name: "Block Years".to_string(),
+        info: "this is how many consecutive historical years are drawn at a time".to_string(),
+        default_val: Opts::Int(default_block_years),
+        optarr: &AGE_RANGE,
+    };
+
+    let default_bear_return = -0.10;
+    let (bear_return, set_bear_return) = create_signal(Opts::Float(default_bear_return));
+    let bear_return_opts = move || OptionMeta {
+        numtype: OptType::Float,
+        name: "Bear Market Return".to_string(),
+        info: "this is the expected yearly stock return in a bear market".to_string(),
+        default_val: Opts::Float(default_bear_return),
+        optarr: &RETURN_RATES,
+    };
 
-    let (owner_savings_arr, set_owner_savings_arr) = create_signal(vec![0.0; 100]);
-    let (renter_savings_arr, set_renter_savings_arr) = create_signal(vec![0.0; 100]);
+    let default_bear_chance = 0.15;
+    let (bear_chance, set_bear_chance) = create_signal(Opts::Float(default_bear_chance));
+    let bear_chance_opts = move || OptionMeta {
+        numtype: OptType::Float,
+        name: "Bear Market Chance".to_string(),
+        info: "this is the chance a bull market turns into a bear market each year".to_string(),
+        default_val: Opts::Float(default_bear_chance),
+        optarr: &CHANCE_RATES,
+    };
+
+    let default_bear_years = 2;
+    let (bear_years, set_bear_years) = create_signal(Opts::Int(default_bear_years));
+    let bear_years_opts = move || OptionMeta {
+        numtype: OptType::Int,
+        name: "Bear Market Years".to_string(),
+        info: "this is how long a bear market lasts on average".to_string(),
+        default_val: Opts::Int(default_bear_years),
+        optarr: &AGE_RANGE,
+    };
+
+    let default_allocation = 0;
+    let (allocation, set_allocation) = create_signal(default_allocation);
+    let allocation_opts = move || ChoiceMeta {
+        name: "Asset Allocation".to_string(),
+        info: "how savings are split between stocks, bonds and cash by age, age bands move from 80% stocks when young to 40% late in life".to_string(),
+        default_val: default_allocation,
+        choices: &["Age Bands", "Target Date", "Custom"],
+    };
+
+    let default_rebalancing = 0;
+    let (rebalancing, set_rebalancing) = create_signal(default_rebalancing);
+    let rebalancing_opts = move || ChoiceMeta {
+        name: "Annual Rebalancing".to_string(),
+        info: "savings are reset to the target allocation every year, otherwise they drift with returns".to_string(),
+        default_val: default_rebalancing,
+        choices: &["Yes", "No"],
+    };
+
+    // breakpoint ages of a custom glide path and the allocation at each
+    let (glide_points, set_glide_points) = create_signal(vec![
+        (default_age, Mix::normalized(0.9, 0.1, 0.0)),
+        (65, Mix::normalized(0.5, 0.45, 0.05)),
+    ]);
+
+    let default_glide_age = 65;
+    let (glide_age, set_glide_age) = create_signal(Opts::Int(default_glide_age));
+    let glide_age_opts = move || OptionMeta {
+        numtype: OptType::Int,
+        name: "Breakpoint Age".to_string(),
+        info: "the allocation moves in a straight line from the breakpoint before this age to this one".to_string(),
+        default_val: Opts::Int(default_glide_age),
+        optarr: &AGE_RANGE,
+    };
+
+    let default_glide_stocks = 0.5;
+    let (glide_stocks, set_glide_stocks) = create_signal(Opts::Float(default_glide_stocks));
+    let glide_stocks_opts = move || OptionMeta {
+        numtype: OptType::Float,
+        name: "Stocks".to_string(),
+        info: "this is the share of savings in stocks at the breakpoint age".to_string(),
+        default_val: Opts::Float(default_glide_stocks),
+        optarr: &ALLOCATION_SHARES,
+    };
+
+    let default_glide_bonds = 0.45;
+    let (glide_bonds, set_glide_bonds) = create_signal(Opts::Float(default_glide_bonds));
+    let glide_bonds_opts = move || OptionMeta {
+        numtype: OptType::Float,
+        name: "Bonds".to_string(),
+        info: "this is the share of savings in bonds at the breakpoint age, the rest is cash".to_string(),
+        default_val: Opts::Float(default_glide_bonds),
+        optarr: &ALLOCATION_SHARES,
+    };
+
+    let new_glide_point = move || {
+        let stocks = glide_stocks.get_untracked().get_float();
+        let bonds = glide_bonds.get_untracked().get_float().min(1.0 - stocks);
+        (glide_age.get_untracked().get_int(), Mix::normalized(stocks, bonds, 1.0 - stocks - bonds))
+    };
+
+    // every rerun draws a new seed, entering a seed reproduces its simulation
+    let (seed, set_seed) = create_signal(new_seed());
 
     let (equivelent_rent, set_equivelent_rent) = create_signal("".to_string());
 
-    let owner_savings = move || {
-        Saver {
-            monthly_rent: 0.0,
-            current_age: age.get_untracked().get_int(),
-            retirement_age: retirement_age.get_untracked().get_int(),
-            total_savings: networth.get_untracked().get_float(),
-            monthly_income: monthly_income.get_untracked().get_float(),
-            monthly_expenses: monthly_expenses.get_untracked().get_float(),
-            home_value: home_value.get_untracked().get_float(),
-            mortgage_debt: mortgage.get_untracked().get_float(),
-            mortgage_rate: mortgage_rate.get_untracked().get_float(),
-            mortgage_term: mortgage_term.get_untracked().get_int(),
-            min_baseline_retirement_income: min_retirement_income.get_untracked().get_float(),
-            max_baseline_retirement_income: max_retirement_income.get_untracked().get_float(),
-            home_expenses: 0.0,
-            home_savings: vec![0.0; DEATH],
-            rental_savings: vec![0.0; DEATH],
-            active_retirement: false,
-            home_owned_age: None::<u8>,
-            cached_mortgage_installment: None::<f32>,
-            interest_rates,
-            inflation_rates,
+    let wage_growth_profile = move || {
+        let growth = wage_growth.get().get_float();
+        match wage_profile.get() {
+            1 => WageGrowth::Linear { rate: growth },
+            2 => WageGrowth::Hump {
+                early_growth: growth,
+                peak_age: peak_earnings_age.get().get_int(),
+            },
+            _ => WageGrowth::Flat,
         }
-        .calculate_savings(SaverType::HomeOwner, DEATH as u8)
-    };
-
-    let renter_savings = move || {
-        Saver {
-            monthly_rent: rent.get_untracked().get_float(),
-            current_age: age.get_untracked().get_int(),
-            retirement_age: retirement_age.get_untracked().get_int(),
-            total_savings: networth.get_untracked().get_float(),
-            monthly_income: monthly_income.get_untracked().get_float(),
-            monthly_expenses: monthly_expenses.get_untracked().get_float(),
-            home_value: 0.0,
-            mortgage_debt: 0.0,
-            mortgage_rate: 0.0,
-            mortgage_term: 0,
-            min_baseline_retirement_income: min_retirement_income.get_untracked().get_float(),
-            max_baseline_retirement_income: max_retirement_income.get_untracked().get_float(),
-            home_expenses: 0.0,
-            home_savings: vec![0.0; DEATH],
-            rental_savings: vec![0.0; DEATH],
-            active_retirement: false,
-            home_owned_age: None::<u8>,
-            cached_mortgage_installment: None::<f32>,
-            interest_rates,
-            inflation_rates,
+    };
+
+    let partner = move || {
+        if has_partner.get() == 0 {
+            return None;
+        }
+        Some(Partner {
+            current_age: partner_age.get().get_int(),
+            retirement_age: partner_retirement_age.get().get_int(),
+            monthly_income: partner_income.get().get_float(),
+            social_security_age: partner_social_security_age.get().get_int(),
+            social_security_benefit: partner_social_security.get().get_float(),
+            pension_age: partner_pension_age.get().get_int(),
+            pension_benefit: partner_pension.get().get_float(),
+            pension_cola: partner_pension_cola.get() == 1,
+        })
+    };
+
+    let returns = move || {
+        let mean = mean_return.get().get_float();
+        let std_dev = return_std_dev.get().get_float();
+        match return_model.get() {
+            1 => Model::Normal(Normal { mean, std_dev }),
+            2 => Model::Lognormal(Lognormal { mean, std_dev }),
+            3 => Model::StudentT(StudentT {
+                mean,
+                std_dev,
+                degrees_of_freedom: degrees_of_freedom.get().get_int(),
+            }),
+            4 => Model::BlockBootstrap(BlockBootstrap {
+                block_years: block_years.get().get_int(),
+            }),
+            5 => Model::RegimeSwitching(RegimeSwitching {
+                bull_mean: mean,
+                bull_std_dev: std_dev,
+                bear_mean: bear_return.get().get_float(),
+                bear_std_dev: std_dev,
+                bull_to_bear: bear_chance.get().get_float(),
+                bear_to_bull: 1.0 / bear_years.get().get_int().max(1) as f32,
+            }),
+            _ => Model::Uniform(Uniform),
         }
-        .calculate_savings(SaverType::Renter, DEATH as u8)
     };
 
-    let calculate_renter_equivelence = move || {
+    let glide_path = move || match allocation.get() {
+        1 => GlidePath::TargetDate,
+        2 => GlidePath::Custom(glide_points.get()),
+        _ => GlidePath::AgeBands,
+    };
 
-        let owner_saved = owner_savings_arr.with_untracked(|owner_saved| *owner_saved.last().unwrap_or(&0.0));
-        let last_age_owner_saved = owner_savings_arr.with_untracked(|owner_saved| 
-            owner_saved.iter().enumerate().find(|(_, x)| **x >= 0.0).map(|(i, _)| i).unwrap_or(0)
-        );
+    let tax_config = move || {
+        let filing_status = match filing_status.get() {
+            1 => FilingStatus::Single,
+            2 => FilingStatus::MarriedJoint,
+            _ => return None,
+        };
+        Some(TaxConfig {
+            filing_status,
+            state_tax_rate: state_tax.get().get_float(),
+        })
+    };
+
+    let strategy = move || {
+        let rate = withdrawal_rate.get().get_float();
+        match withdrawal_strategy.get() {
+            0 => Strategy::ConstantDollar(ConstantDollar { rate }),
+            2 => Strategy::GuytonKlinger(GuytonKlinger {
+                initial_rate: rate,
+                guardrail: GUARDRAIL,
+                adjustment: GUARDRAIL_ADJUSTMENT,
+            }),
+            3 => Strategy::Vpw(Vpw {
+                real_return: VPW_REAL_RETURN,
+                end_age: DEATH as u8,
+            }),
+            4 => Strategy::Rmd(RmdSpending),
+            _ => Strategy::ConstantPercent(ConstantPercent { rate }),
+        }
+    };
+
+    // owner and renter templates, the simulation fills in the rate paths for each run
+    let owner_saver = move || Saver {
+        monthly_rent: 0.0,
+        current_age: age.get().get_int(),
+        retirement_age: retirement_age.get().get_int(),
+        total_savings: networth.get().get_float(),
+        monthly_income: monthly_income.get().get_float(),
+        monthly_expenses: monthly_expenses.get().get_float(),
+        home_value: home_value.get().get_float(),
+        home_appreciation_rate: home_appreciation.get().get_float(),
+        mortgage_debt: mortgage.get().get_float(),
+        mortgage_rate: mortgage_rate.get().get_float(),
+        mortgage_term: mortgage_term.get().get_int(),
+        mortgage_start_age: age.get().get_int(),
+        arm: match arm_fixed_years.get().get_int() {
+            0 => None,
+            fixed_years => Some(Arm {
+                fixed_years,
+                margin: arm_margin.get().get_float(),
+                periodic_cap: arm_periodic_cap.get().get_float(),
+                lifetime_cap: arm_lifetime_cap.get().get_float(),
+                initial_rate: mortgage_rate.get().get_float(),
+            }),
+        },
+        min_baseline_retirement_income: min_retirement_income.get().get_float(),
+        max_baseline_retirement_income: max_retirement_income.get().get_float(),
+        property_tax_rate: property_tax.get().get_float(),
+        maintenance_rate: maintenance.get().get_float(),
+        monthly_hoa: hoa.get().get_float(),
+        monthly_insurance: insurance.get().get_float(),
+        closing_cost_rate: closing_costs.get().get_float(),
+        selling_cost_rate: selling_costs.get().get_float(),
+        sale_age: sale_age.get().get_age(),
+        rent_after_sale: rent.get().get_float(),
+        purchase_age: None,
+        purchase_home_value: 0.0,
+        down_payment_rate: 0.0,
+        // 0 turns off the scheduled refinance and the rate drop trigger
+        refinance_age: refinance_age.get().get_age(),
+        refinance_rate_drop: Some(refinance_rate_drop.get().get_float()).filter(|drop| *drop != 0.0),
+        refinance_term: refinance_term.get().get_int(),
+        refinance_cost_rate: refinance_costs.get().get_float(),
+        extra_monthly_principal: extra_monthly_principal.get().get_float(),
+        extra_annual_principal: extra_annual_principal.get().get_float(),
+        mortgage_interest_paid: 0.0,
+        pmi_rate: pmi_rate.get().get_float(),
+        pmi_ltv_cutoff: pmi_ltv_cutoff.get().get_float(),
+        pmi_end_age: None,
+        tax: tax_config(),
+        price_level: 1.0,
+        cost_basis: 0.0,
+        home_cost_basis: 0.0,
+        home_sale_exclusion: home_sale_exclusion.get().get_float(),
+        pre_tax_savings: pre_tax_savings.get().get_float(),
+        roth_savings: roth_savings.get().get_float(),
+        pre_tax_contribution_rate: pre_tax_contribution.get().get_float(),
+        roth_contribution_rate: roth_contribution.get().get_float(),
+        employer_match_rate: employer_match.get().get_float(),
+        rmd_age: rmd_age.get().get_int(),
+        withdrawal_order: WITHDRAWAL_ORDERS[withdrawal_order.get() as usize],
+        withdrawal_strategy: strategy(),
+        annual_withdrawal: None,
+        withdrawal_error: None,
+        social_security_age: social_security_age.get().get_int(),
+        social_security_benefit: social_security.get().get_float(),
+        pension_age: pension_age.get().get_int(),
+        pension_benefit: pension.get().get_float(),
+        pension_cola: pension_cola.get() == 1,
+        retirement_expense_rate: 1.0 + retirement_expense_change.get().get_float(),
+        healthcare_cost: healthcare.get().get_float(),
+        healthcare_inflation: healthcare_inflation.get().get_float(),
+        spending_smile: spending_smile.get() == 1,
+        life_events: life_events.get(),
+        wage_growth: wage_growth_profile(),
+        wage_volatility: wage_volatility.get().get_float(),
+        wage_market_correlation: wage_market_correlation.get().get_float(),
+        partner: partner(),
+        allocation: glide_path(),
+        annual_rebalancing: rebalancing.get() == 0,
+        home_savings: vec![0.0; DEATH],
+        rental_savings: vec![0.0; DEATH],
+        buy_later_savings: vec![0.0; DEATH],
+        mortgage_payments: vec![0.0; DEATH],
+        active_retirement: false,
+        home_owned_age: None::<u8>,
+        cached_mortgage_installment: None::<f32>,
+        interest_rates: &[],
+        inflation_rates: &[],
+        mortgage_rates: &[],
+        index_rates: &[],
+        market_shocks: &[],
+        wage_noise: &[],
+        rent_growth_rates: &[],
+        home_growth_rates: &[],
+    };
+
+    // rents for the whole run, with none of the owner's home, mortgage or home cost settings
+    let renter_saver = move || Saver {
+        monthly_rent: rent.get().get_float(),
+        home_value: 0.0,
+        home_appreciation_rate: 0.0,
+        mortgage_debt: 0.0,
+        mortgage_rate: 0.0,
+        mortgage_term: 0,
+        arm: None,
+        property_tax_rate: 0.0,
+        maintenance_rate: 0.0,
+        monthly_hoa: 0.0,
+        monthly_insurance: 0.0,
+        closing_cost_rate: 0.0,
+        selling_cost_rate: 0.0,
+        sale_age: None,
+        rent_after_sale: 0.0,
+        refinance_age: None,
+        refinance_rate_drop: None,
+        refinance_term: 0,
+        refinance_cost_rate: 0.0,
+        extra_monthly_principal: 0.0,
+        extra_annual_principal: 0.0,
+        pmi_rate: 0.0,
+        pmi_ltv_cutoff: 0.0,
+        home_sale_exclusion: 0.0,
+        ..owner_saver()
+    };
+
+    // rents until the purchase age, then buys with the owner's mortgage and home cost settings
+    let buy_later_saver = move || Saver {
+        monthly_rent: rent.get().get_float(),
+        home_value: 0.0,
+        mortgage_debt: 0.0,
+        purchase_age: Some(purchase_age.get().get_int()),
+        purchase_home_value: purchase_home_value.get().get_float(),
+        down_payment_rate: down_payment.get().get_float(),
+        ..owner_saver()
+    };
+
+    // find the rent that matches the owner's savings on the median rate path
+    let calculate_renter_equivelence = move |rates: &RatePath| {
+
+        let owner_saved = owner_saver()
+            .with_rates(rates)
+            .calculate_savings(SaverType::HomeOwner, DEATH as u8);
+        let last_age_owner_saved = owner_saved.iter().rposition(|x| *x > 0.0).unwrap_or(0);
 
         let adjust_rent_directionally = |rent: &f32, sign: &f32| {
             rent + (0.1 * sign)
@@ -273,56 +1294,93 @@ fn App() -> impl IntoView {
         };
 
         let mut rent = rent.with_untracked(|rent| rent.get_float());
-        let current_age = age.with_untracked(|age| age.get_int());
-        let retirement_age = retirement_age.with_untracked(|retirement_age| retirement_age.get_int());
-        let networth = networth.with_untracked(|networth| networth.get_float());
-        let monthly_income = monthly_income.with_untracked(|monthly_income| monthly_income.get_float());
-        let monthly_expenses = monthly_expenses.with_untracked(|monthly_expenses| monthly_expenses.get_float());
-        let _home_value = home_value.with_untracked(|home_value| home_value.get_float());
-        let _mortgage = mortgage.with_untracked(|mortgage| mortgage.get_float());
-        let _mortgage_rate = mortgage_rate.with_untracked(|mortgage_rate| mortgage_rate.get_float());
-        let _mortgage_term = mortgage_term.with_untracked(|mortgage_term| mortgage_term.get_int());
-        let min_retirement_income = min_retirement_income.with_untracked(|min_retirement_income| min_retirement_income.get_float());
-        let max_retirement_income = max_retirement_income.with_untracked(|max_retirement_income| max_retirement_income.get_float());
-        let mut rs = vec![0.0; DEATH];
-        let mut continue_adj = true;
         for _ in 0..1000 {
-            rs = Saver {
+            let rs = Saver {
                 monthly_rent: rent,
-                current_age,
-                retirement_age,
-                total_savings: networth,
-                monthly_income,
-                monthly_expenses,
-                home_value: 0.0,
-                mortgage_debt: 0.0,
-                mortgage_rate: 0.0,
-                mortgage_term: 0,
-                min_baseline_retirement_income: min_retirement_income,
-                max_baseline_retirement_income: max_retirement_income,
-                home_expenses: 0.0,
-                home_savings: vec![0.0; DEATH],
-                rental_savings: vec![0.0; DEATH],
-                active_retirement: false,
-                home_owned_age: None::<u8>,
-                cached_mortgage_installment: None::<f32>,
-                interest_rates,
-                inflation_rates,
-            }.calculate_savings(SaverType::Renter, DEATH as u8);
-
-            (rent, continue_adj) = adjust_rent(&rent, &owner_saved, &rs[last_age_owner_saved]);
+                ..renter_saver()
+            }.with_rates(rates).calculate_savings(SaverType::Renter, DEATH as u8);
+
+            let continue_adj;
+            (rent, continue_adj) = adjust_rent(&rent, &owner_saved[last_age_owner_saved], &rs[last_age_owner_saved]);
 
             if !continue_adj {
                 break;
             }
-        }
-        set_equivelent_rent.set(format!(
-            "Home:{}\nRent: {}", 
-            (home_value.get().get_float().trunc() as i32).to_formatted_string(&Locale::en),
-            (rent.trunc() as i32).to_formatted_string(&Locale::en)
-        ));
-        rs
-    };
+        }
+        set_equivelent_rent.set(format!(
+            "Home:{}\nRent: {}", 
+            (home_value.get_untracked().get_float().trunc() as i32).to_formatted_string(&Locale::en),
+            (rent.trunc() as i32).to_formatted_string(&Locale::en)
+        ));
+        rent
+    };
+
+    // every input the simulation reads, the builders read them tracked
+    let track_inputs = move || {
+        rate_paths.get();
+        asset_model.track();
+        returns();
+        owner_saver();
+        renter_saver();
+        buy_later_saver();
+    };
+
+    // a burst of input changes reruns the simulation once they settle, the chart says it's simulating until then
+    let (settled, set_settled) = create_signal(0_u32);
+    let (simulating, set_simulating) = create_signal(false);
+    let pending_rerun = store_value(None::<leptos::leptos_dom::helpers::TimeoutHandle>);
+    create_effect(move |first: Option<()>| {
+        track_inputs();
+        // the first simulation runs right away
+        if first.is_none() {
+            return;
+        }
+        set_simulating.set(true);
+        if let Some(handle) = pending_rerun.get_value() {
+            handle.clear();
+        }
+        pending_rerun.set_value(
+            set_timeout_with_handle(
+                move || {
+                    set_settled.update(|settled| *settled += 1);
+                    set_simulating.set(false);
+                },
+                Duration::from_millis(SIMULATION_DELAY_MS),
+            )
+            .ok(),
+        );
+    });
+
+    // rerun the monte carlo simulation when the inputs settle or the seed changes
+    let simulation = create_memo(move |_| {
+        let seed = seed.get();
+        settled.track();
+        let find_equivelent_rent = find_equivelent_rent.get();
+        // the inputs are read when they settle, not as they change
+        untrack(move || {
+            let paths = rate_paths.get_untracked();
+            let assets = asset_model.get_untracked();
+            let model = returns();
+            let run = |owner: &Saver, renter: &Saver, buy_later: &Saver| {
+                match paths {
+                    1 => backtest(owner, renter, buy_later),
+                    2 => correlated_monte_carlo(owner, renter, buy_later, MONTE_CARLO_RUNS, seed, &assets),
+                    _ => monte_carlo(owner, renter, buy_later, MONTE_CARLO_RUNS, seed, &model),
+                }
+            };
+            let simulation = run(&owner_saver(), &renter_saver(), &buy_later_saver());
+            if find_equivelent_rent {
+                let equivelent_rent = calculate_renter_equivelence(&simulation.rates);
+                let renter = Saver {
+                    monthly_rent: equivelent_rent,
+                    ..renter_saver()
+                };
+                run(&owner_saver(), &renter, &buy_later_saver())
+            } else {
+                simulation
+            }
+        })
+    });
 
     let default_y_axis_max = 10000000.0;
     let (y_axis_max, set_y_axis_max) = create_signal(Opts::Float(default_y_axis_max));
@@ -342,7 +1400,9 @@ fn App() -> impl IntoView {
 
             // x axis data / format
             let start_x_value = age.get_untracked().get_int() as usize;
-            let x_values = &AGE_RANGE_FLOATS[start_x_value..DEATH];
+            // the run goes past your death age while a younger partner lives
+            let last_x_value = simulation.with_untracked(|simulation| simulation.owner.p50.len());
+            let x_values = &(start_x_value..last_x_value).map(|age| age as f32).collect::<Vec<f32>>();
             let x_axis = || Axis::new().title("Age".into());
             let y_axis = || {
                 Axis::new()
@@ -352,221 +1412,74 @@ fn App() -> impl IntoView {
             };
 
             let annotations = || {
-                // function to calculate avg returns for annotation
-                let avg_returns = |start: usize, stop: usize| {
-                    interest_rates.with_untracked(|interest_rates| {
-                        interest_rates[start..stop]
-                            .iter()
-                            .map(|x| x.get_float_ref())
-                            .sum::<f32>()
-                            / (stop - start) as f32
+                // the age spans follow the glide path the run used, the last one ends with the household's run
+                let owner = untrack(owner_saver);
+                let spans = owner.allocation.spans(
+                    owner.current_age,
+                    owner.retirement_age,
+                    Household::last_age(&owner, DEATH as u8),
+                );
+
+                // median returns from the first to the last age of a span
+                let span_returns = |first: u8, last: u8| {
+                    simulation.with_untracked(|simulation| {
+                        let interest = &simulation.rates.interest;
+                        interest[(first as usize).min(interest.len())..(last as usize + 1).min(interest.len())].to_vec()
                     })
                 };
 
-                // function to calculate std dev for annotation
-                let std_dev = |start: usize, stop: usize| {
-                    interest_rates.with_untracked(|interest_rates| {
-                        interest_rates[start..stop]
-                            .iter()
-                            .map(|x| x.get_float_ref())
-                            .fold(0.0, |acc, x| acc + (x - avg_returns(start, stop)).powi(2))
-                            .sqrt()
-                            / (stop - start) as f32
-                    })
+                // function to calculate avg returns for annotation
+                let avg_returns = |first: u8, last: u8| {
+                    let returns = span_returns(first, last);
+                    returns.iter().sum::<f32>() / returns.len().max(1) as f32
                 };
 
-                let x_pos = |_: usize, stop: usize| {
-                    (stop as f32 - start_x_value as f32) / (DEATH - start_x_value) as f32 - 0.05
+                // function to calculate std dev for annotation
+                let std_dev = |first: u8, last: u8| {
+                    let returns = span_returns(first, last);
+                    returns
+                        .iter()
+                        .fold(0.0, |acc, x| acc + (x - avg_returns(first, last)).powi(2))
+                        .sqrt()
+                        / returns.len().max(1) as f32
                 };
 
-                // adding annotations for avg return
-                let avg_return_annotate = || {
-                    let offset = 0.225;
-                    [
-                        plotly::layout::Annotation::new()
-                            .text(format!("{:.1}%", avg_returns(0, 35) * 100.0,))
-                            .x_ref("paper")
-                            .x(0.05)
-                            .y_ref("paper")
-                            .y(0.0 - offset)
-                            .show_arrow(false)
-                            .text_angle(27.0)
-                            .font(Font::new().size(10).color(NamedColor::DarkSeaGreen)),
-                        plotly::layout::Annotation::new()
-                            .text(format!("{:.1}%", avg_returns(36, 49) * 100.0,))
-                            .x_ref("paper")
-                            .x(x_pos(36, 49))
-                            .y_ref("paper")
-                            .y(0.0 - offset)
-                            .show_arrow(false)
-                            .text_angle(27.0)
-                            .font(Font::new().size(10).color(NamedColor::DarkSeaGreen)),
-                        plotly::layout::Annotation::new()
-                            .text(format!("{:.1}%", avg_returns(50, 64) * 100.0,))
-                            .x_ref("paper")
-                            .x(x_pos(50, 64))
-                            .y_ref("paper")
-                            .y(0.0 - offset)
-                            .show_arrow(false)
-                            .text_angle(27.0)
-                            .font(Font::new().size(10).color(NamedColor::DarkSeaGreen)),
-                        plotly::layout::Annotation::new()
-                            .text(format!("{:.1}%", avg_returns(65, 80) * 100.0,))
-                            .x_ref("paper")
-                            .x(x_pos(65, 80))
-                            .y_ref("paper")
-                            .y(0.0 - offset)
-                            .show_arrow(false)
-                            .text_angle(27.0)
-                            .font(Font::new().size(10).color(NamedColor::DarkSeaGreen)),
-                        plotly::layout::Annotation::new()
-                            .text(format!("{:.1}%", avg_returns(81, DEATH) * 100.0,))
-                            .x_ref("paper")
-                            .x(x_pos(81, DEATH))
-                            .y_ref("paper")
-                            .y(0.0 - offset)
-                            .show_arrow(false)
-                            .text_angle(27.0)
-                            .font(Font::new().size(10).color(NamedColor::DarkSeaGreen)),
-                        plotly::layout::Annotation::new()
-                            .text("ROI")
-                            .x_ref("paper")
-                            .x(-0.05)
-                            .y_ref("paper")
-                            .y(0.0 - offset)
-                            .show_arrow(false)
-                            .text_angle(0.0)
-                            .background_color(NamedColor::DarkSeaGreen)
-                            .opacity(0.5)
-                            .font(Font::new().size(10).color(NamedColor::FloralWhite)),
-                    ]
+                let x_pos = |first: u8, last: u8| {
+                    ((first as f32 + last as f32) / 2.0 - start_x_value as f32) / (last_x_value - start_x_value) as f32
                 };
 
-                // adding annotations for std dev
-                let std_dev_annotate = || {
-                    let offset = 0.3;
-                    [
-                        plotly::layout::Annotation::new()
-                            .text(format!("{:.1}%", std_dev(0, 35) * 100.0,))
-                            .x_ref("paper")
-                            .x(0.05)
-                            .y_ref("paper")
-                            .y(0.0 - offset)
-                            .show_arrow(false)
-                            .text_angle(27.0)
-                            .font(Font::new().size(10).color(NamedColor::LightSalmon)),
-                        plotly::layout::Annotation::new()
-                            .text(format!("{:.1}%", std_dev(36, 49) * 100.0,))
-                            .x_ref("paper")
-                            .x(x_pos(36, 49))
-                            .y_ref("paper")
-                            .y(0.0 - offset)
-                            .show_arrow(false)
-                            .text_angle(27.0)
-                            .font(Font::new().size(10).color(NamedColor::LightSalmon)),
-                        plotly::layout::Annotation::new()
-                            .text(format!("{:.1}%", std_dev(50, 64) * 100.0,))
-                            .x_ref("paper")
-                            .x(x_pos(50, 64))
-                            .y_ref("paper")
-                            .y(0.0 - offset)
-                            .show_arrow(false)
-                            .text_angle(27.0)
-                            .font(Font::new().size(10).color(NamedColor::LightSalmon)),
-                        plotly::layout::Annotation::new()
-                            .text(format!("{:.1}%", std_dev(65, 80) * 100.0,))
-                            .x_ref("paper")
-                            .x(x_pos(65, 80))
-                            .y_ref("paper")
-                            .y(0.0 - offset)
-                            .show_arrow(false)
-                            .text_angle(27.0)
-                            .font(Font::new().size(10).color(NamedColor::LightSalmon)),
-                        plotly::layout::Annotation::new()
-                            .text(format!("{:.1}%", std_dev(81, DEATH) * 100.0,))
-                            .x_ref("paper")
-                            .x(x_pos(81, DEATH))
-                            .y_ref("paper")
-                            .y(0.0 - offset)
-                            .show_arrow(false)
-                            .text_angle(27.0)
-                            .font(Font::new().size(10).color(NamedColor::LightSalmon)),
+                // a row of annotations under the chart, one value per age span after the row's label
+                let annotate = |label: &str, color: NamedColor, offset: f64, value: &dyn Fn(u8, u8) -> String| {
+                    let mut row = spans
+                        .iter()
+                        .map(|(first, last)| {
+                            plotly::layout::Annotation::new()
+                                .text(value(*first, *last))
+                                .x_ref("paper")
+                                .x(x_pos(*first, *last))
+                                .y_ref("paper")
+                                .y(0.0 - offset)
+                                .show_arrow(false)
+                                .text_angle(27.0)
+                                .font(Font::new().size(10).color(color))
+                        })
+                        .collect::<Vec<_>>();
+                    row.push(
                         plotly::layout::Annotation::new()
-                            .text("Beta")
+                            .text(label)
                             .x_ref("paper")
                             .x(-0.05)
                             .y_ref("paper")
                             .y(0.0 - offset)
                             .show_arrow(false)
                             .text_angle(0.0)
-                            .background_color(NamedColor::LightSalmon)
+                            .background_color(color)
                             .opacity(0.5)
                             .font(Font::new().size(10).color(NamedColor::FloralWhite)),
-                    ]
+                    );
+                    row
                 };
 
-                // adding annotations for std dev
-                let age_annotate = || {
-                    let offset = 0.15;
-                    [
-                        plotly::layout::Annotation::new()
-                            .text(format!("{}-{}", start_x_value, 35))
-                            .x_ref("paper")
-                            .x(0.05)
-                            .y_ref("paper")
-                            .y(0.0 - offset)
-                            .show_arrow(false)
-                            .text_angle(27.0)
-                            .font(Font::new().size(10).color(NamedColor::BurlyWood)),
-                        plotly::layout::Annotation::new()
-                            .text(format!("{}-{}", 36, 49))
-                            .x_ref("paper")
-                            .x(x_pos(36, 49))
-                            .y_ref("paper")
-                            .y(0.0 - offset)
-                            .show_arrow(false)
-                            .text_angle(27.0)
-                            .font(Font::new().size(10).color(NamedColor::BurlyWood)),
-                        plotly::layout::Annotation::new()
-                            .text(format!("{}-{}", 50, 64))
-                            .x_ref("paper")
-                            .x(x_pos(50, 64))
-                            .y_ref("paper")
-                            .y(0.0 - offset)
-                            .show_arrow(false)
-                            .text_angle(27.0)
-                            .font(Font::new().size(10).color(NamedColor::BurlyWood)),
-                        plotly::layout::Annotation::new()
-                            .text(format!("{}-{}", 65, 80))
-                            .x_ref("paper")
-                            .x(x_pos(65, 80))
-                            .y_ref("paper")
-                            .y(0.0 - offset)
-                            .show_arrow(false)
-                            .text_angle(27.0)
-                            .font(Font::new().size(10).color(NamedColor::BurlyWood)),
-                        plotly::layout::Annotation::new()
-                            .text(format!("{}-{}", 81, DEATH))
-                            .x_ref("paper")
-                            .x(x_pos(81, DEATH))
-                            .y_ref("paper")
-                            .y(0.0 - offset)
-                            .show_arrow(false)
-                            .text_angle(27.0)
-                            .font(Font::new().size(10).color(NamedColor::BurlyWood)),
-                        plotly::layout::Annotation::new()
-                            .text("Age")
-                            .x_ref("paper")
-                            .x(-0.05)
-                            .y_ref("paper")
-                            .y(0.0 - offset)
-                            .show_arrow(false)
-                            .text_angle(0.0)
-                            .background_color(NamedColor::BurlyWood)
-                            .opacity(0.5)
-                            .font(Font::new().size(10).color(NamedColor::FloralWhite)),
-                    ]
-                };
                 let trace_annotations = || {
                     [
                         plotly::layout::Annotation::new()
@@ -587,6 +1500,15 @@ fn App() -> impl IntoView {
                             .show_arrow(false)
                             .text_angle(0.0)
                             .font(Font::new().size(12).color(NamedColor::LightSalmon)),
+                        plotly::layout::Annotation::new()
+                            .text("Buy Later")
+                            .x_ref("paper")
+                            .x(0.9)
+                            .y_ref("paper")
+                            .y(0.8)
+                            .show_arrow(false)
+                            .text_angle(0.0)
+                            .font(Font::new().size(12).color(NamedColor::SteelBlue)),
                         plotly::layout::Annotation::new()
                             .text(equivelent_rent.get())
                             .x_ref("paper")
@@ -600,74 +1522,154 @@ fn App() -> impl IntoView {
                 };
 
                 let mut annotation_vec = vec![];
-                match age.get_untracked().get_int() {
-                    81.. => {
-                        annotation_vec.extend_from_slice(&age_annotate()[4..]);
-                        annotation_vec.extend_from_slice(&avg_return_annotate()[4..]);
-                        annotation_vec.extend_from_slice(&std_dev_annotate()[4..]);
-                    }
-                    65..=80 => {
-                        annotation_vec.extend_from_slice(&age_annotate()[3..]);
-                        annotation_vec.extend_from_slice(&avg_return_annotate()[3..]);
-                        annotation_vec.extend_from_slice(&std_dev_annotate()[3..]);
-                    }
-                    50..=64 => {
-                        annotation_vec.extend_from_slice(&age_annotate()[2..]);
-                        annotation_vec.extend_from_slice(&avg_return_annotate()[2..]);
-                        annotation_vec.extend_from_slice(&std_dev_annotate()[2..]);
-                    }
-                    36..=49 => {
-                        annotation_vec.extend_from_slice(&age_annotate()[1..]);
-                        annotation_vec.extend_from_slice(&avg_return_annotate()[1..]);
-                        annotation_vec.extend_from_slice(&std_dev_annotate()[1..]);
-                    }
-                    0..=35 => {
-                        annotation_vec.extend_from_slice(&age_annotate()[0..]);
-                        annotation_vec.extend_from_slice(&avg_return_annotate()[0..]);
-                        annotation_vec.extend_from_slice(&std_dev_annotate()[0..]);
-                    }
-                }
+                annotation_vec.extend(annotate("Age", NamedColor::BurlyWood, 0.15, &|first, last| {
+                    format!("{}-{}", first, last)
+                }));
+                annotation_vec.extend(annotate("ROI", NamedColor::DarkSeaGreen, 0.225, &|first, last| {
+                    format!("{:.1}%", avg_returns(first, last) * 100.0)
+                }));
+                annotation_vec.extend(annotate("Beta", NamedColor::LightSalmon, 0.3, &|first, last| {
+                    format!("{:.1}%", std_dev(first, last) * 100.0)
+                }));
                 annotation_vec.extend_from_slice(&trace_annotations());
 
                 annotation_vec
             };
 
-            // savings values to plot (owner and renter)
+            // savings bands to plot (owner and renter), median line with a shaded 10th-90th percentile band
             let traces = || {
-                let owner_trace = Scatter::new(
-                    x_values.to_vec(),
-                    owner_savings_arr.get_untracked()[start_x_value..DEATH].to_vec(),
-                )
-                .visible(plotly::common::Visible::True)
-                .fill_color(NamedColor::DarkSeaGreen)
-                .line(
-                    Line::new()
-                        .dash(DashType::Solid)
-                        .color(NamedColor::DarkSeaGreen),
-                )
-                .name("Owner");
-
-                let renter_trace = Scatter::new(
-                    x_values.to_vec(),
-                    renter_savings_arr.get_untracked()[start_x_value..DEATH].to_vec(),
-                )
-                .visible(plotly::common::Visible::True)
-                .fill_color(NamedColor::LightSalmon)
-                .line(
-                    Line::new()
-                        .dash(DashType::Dash)
-                        .color(NamedColor::LightSalmon),
-                )
-                .name("Renter");
-
-                (owner_trace, renter_trace)
+                let band = |bands: &Bands, color: NamedColor, fill: Rgba, dash: DashType, name: &str| {
+                    let lower = Scatter::new(
+                        x_values.to_vec(),
+                        bands.p10[start_x_value..last_x_value].to_vec(),
+                    )
+                    .line(Line::new().width(0.0).color(color))
+                    .name(format!("{} 10th percentile", name));
+
+                    let upper = Scatter::new(
+                        x_values.to_vec(),
+                        bands.p90[start_x_value..last_x_value].to_vec(),
+                    )
+                    .fill(Fill::ToNextY)
+                    .fill_color(fill)
+                    .line(Line::new().width(0.0).color(color))
+                    .name(format!("{} 90th percentile", name));
+
+                    let median = Scatter::new(
+                        x_values.to_vec(),
+                        bands.p50[start_x_value..last_x_value].to_vec(),
+                    )
+                    .visible(plotly::common::Visible::True)
+                    .line(Line::new().dash(dash).color(color))
+                    .name(format!("{} median", name));
+
+                    [lower, upper, median]
+                };
+
+                simulation.with_untracked(|simulation| {
+                    let mut traces = band(
+                        &simulation.owner,
+                        NamedColor::DarkSeaGreen,
+                        Rgba::new(143, 188, 143, 0.3),
+                        DashType::Solid,
+                        "Owner",
+                    )
+                    .to_vec();
+                    traces.extend(band(
+                        &simulation.renter,
+                        NamedColor::LightSalmon,
+                        Rgba::new(255, 160, 122, 0.3),
+                        DashType::Dash,
+                        "Renter",
+                    ));
+                    traces.extend(band(
+                        &simulation.buy_later,
+                        NamedColor::SteelBlue,
+                        Rgba::new(70, 130, 180, 0.2),
+                        DashType::DashDot,
+                        "Buy Later",
+                    ));
+
+                    // investing instead of prepaying, compared against the prepaying owner
+                    if extra_monthly_principal.get_untracked().get_float() != 0.0
+                        || extra_annual_principal.get_untracked().get_float() != 0.0
+                    {
+                        traces.push(
+                            Scatter::new(
+                                x_values.to_vec(),
+                                simulation.owner_invest.p50[start_x_value..last_x_value].to_vec(),
+                            )
+                            .line(Line::new().dash(DashType::LongDash).color(NamedColor::DarkOliveGreen))
+                            .name("Owner investing instead of prepaying"),
+                        );
+                    }
+
+                    // mark the sale on the owner's median trace
+                    let sold_at = sale_age.get_untracked().get_int() as usize;
+                    if (start_x_value + 1..last_x_value).contains(&sold_at) {
+                        traces.push(
+                            Scatter::new(
+                                vec![AGE_RANGE_FLOATS[sold_at]],
+                                vec![simulation.owner.p50[sold_at]],
+                            )
+                            .mode(Mode::MarkersText)
+                            .text("Sold")
+                            .text_position(Position::TopCenter)
+                            .marker(Marker::new().size(10).color(NamedColor::DarkSeaGreen))
+                            .name("Owner sale"),
+                        );
+                    }
+
+                    // mark each life event where it starts on the owner's median trace
+                    let events = life_events.get_untracked();
+                    let events = events
+                        .iter()
+                        .filter(|event| (start_x_value..last_x_value).contains(&(event.start_age() as usize)))
+                        .collect::<Vec<_>>();
+                    if !events.is_empty() {
+                        traces.push(
+                            Scatter::new(
+                                events.iter().map(|event| AGE_RANGE_FLOATS[event.start_age() as usize]).collect(),
+                                events.iter().map(|event| simulation.owner.p50[event.start_age() as usize]).collect(),
+                            )
+                            .mode(Mode::MarkersText)
+                            .text_array(events.iter().map(|event| event.label()).collect())
+                            .text_position(Position::BottomCenter)
+                            .marker(Marker::new().size(8).color(NamedColor::DimGray))
+                            .name("Life events"),
+                        );
+                    }
+
+                    // median monthly mortgage payments on the right axis to show adjustable rate resets
+                    if arm_fixed_years.get_untracked().get_int() != 0 {
+                        traces.push(
+                            Scatter::new(
+                                x_values.to_vec(),
+                                simulation.owner_payments.p50[start_x_value..last_x_value].to_vec(),
+                            )
+                            .y_axis("y2")
+                            .line(Line::new().dash(DashType::Dot).color(NamedColor::DarkSeaGreen))
+                            .name("Owner mortgage payment"),
+                        );
+                        traces.push(
+                            Scatter::new(
+                                x_values.to_vec(),
+                                simulation.buy_later_payments.p50[start_x_value..last_x_value].to_vec(),
+                            )
+                            .y_axis("y2")
+                            .line(Line::new().dash(DashType::Dot).color(NamedColor::SteelBlue))
+                            .name("Buy later mortgage payment"),
+                        );
+                    }
+                    traces
+                })
             };
 
             let apply_layout = move || async move {
 
-                let traces = traces();
-                plot.add_trace(traces.0);
-                plot.add_trace(traces.1);
+                for trace in traces() {
+                    plot.add_trace(trace);
+                }
 
                 let general_layout = || {
                     plotly::Layout::new()
@@ -676,6 +1678,13 @@ fn App() -> impl IntoView {
                         .annotations(annotations())
                         .x_axis(x_axis())
                         .y_axis(y_axis())
+                        .y_axis2(
+                            Axis::new()
+                                .title("Mortgage Payment".into())
+                                .overlaying("y")
+                                .side(AxisSide::Right)
+                                .visible(arm_fixed_years.get_untracked().get_int() != 0),
+                        )
                         .show_legend(false)
                 };
 
@@ -703,62 +1712,98 @@ fn App() -> impl IntoView {
         },
     );
 
-    let savers_derived = create_memo(move |_| {
-        set_owner_savings_arr.set(owner_savings());
-        if find_equivelent_rent.get() {
-            set_renter_savings_arr.set(calculate_renter_equivelence());
-            plot_resource.refetch();
-        } else {
-            set_renter_savings_arr.set(renter_savings());
+    // first month owner costs, one line per cost
+    let cost_breakdown = move || {
+        simulation.track();
+        let mut owner = untrack(owner_saver);
+        Owner::start_pmi(&mut owner);
+        let costs = Owner::monthly_home_costs(&owner);
+        vec![
+            ("Mortgage Payment", Owner::mortgage_installments(&owner)),
+            ("Property Tax", costs.property_tax),
+            ("Maintenance", costs.maintenance),
+            ("HOA Dues", costs.hoa),
+            ("Home Insurance", costs.insurance),
+            ("PMI", costs.pmi),
+            ("Total", Owner::mortgage_installments(&owner) + costs.total()),
+        ]
+    };
+
+    // invest vs prepay on the median rate path, only when extra principal is paid
+    let prepay_summary = move || {
+        let owner = untrack(owner_saver);
+        if owner.extra_monthly_principal == 0.0 && owner.extra_annual_principal == 0.0 {
+            return vec![];
         }
-    });
+        let comparison = simulation.with(|simulation| prepay_comparison(&owner, &simulation.rates));
+        let age_or_never = |age: Option<u8>| age.map(|age| age.to_string()).unwrap_or("never".to_string());
+        vec![
+            ("Payoff Age Prepaying", age_or_never(comparison.payoff_age)),
+            ("Payoff Age Investing", age_or_never(comparison.invest_payoff_age)),
+            (
+                "Interest Saved",
+                (comparison.interest_saved().round() as i32).to_formatted_string(&Locale::en),
+            ),
+        ]
+    };
 
-    let Pausable{
-        pause,
-        resume,
-        is_active: _,
-    } = use_interval_fn_with_options(
-        move || {
-            if !find_equivelent_rent.get() {
-                let rates = new_rates();
-                set_interest_rates.update(|i| *i = rates.0);
-                set_inflation_rates.update(|i| *i = rates.1);
-                set_owner_savings_arr.update(|i| *i = owner_savings());
-                set_renter_savings_arr.update(|i| *i = renter_savings());
+    // how often savings lasted the whole run, with the worst start year when replaying history
+    let outcome_summary = move || {
+        simulation.with(|simulation| {
+            if rate_paths.get_untracked() == 1 && simulation.start_years.is_empty() {
+                let last_year = HISTORY_START_YEAR as usize + HISTORICAL_RETURNS.len() - 1;
+                return vec![(
+                    "Historical Backtest",
+                    format!(
+                        "no start year fits, the run is longer than the history from {} to {}",
+                        HISTORY_START_YEAR, last_year
+                    ),
+                )];
             }
-        },
-        2000_u64,
-        UseIntervalFnOptions {
-            immediate: true,
-            immediate_callback: true,
-        },
-    );
-    
+            [
+                ("Owner Success Rate", simulation.owner_outcome),
+                ("Renter Success Rate", simulation.renter_outcome),
+                ("Buy Later Success Rate", simulation.buy_later_outcome),
+            ]
+            .into_iter()
+            .map(|(name, outcome)| {
+                let worst = match simulation.start_years.get(outcome.worst_run) {
+                    Some(year) => format!(
+                        ", worst start year {} ending with {} in today's dollars",
+                        year,
+                        (outcome.worst_ending.round() as i32).to_formatted_string(&Locale::en),
+                    ),
+                    None => "".to_string(),
+                };
+                (name, format!("{:.1}%{}", outcome.success_rate * 100.0, worst))
+            })
+            .collect::<Vec<_>>()
+        })
+    };
+
+    // first month income taxes, shows what the owner's deductions save
+    let tax_summary = move || {
+        simulation.track();
+        if untrack(tax_config).is_none() {
+            return vec![];
+        }
+        let owner = untrack(owner_saver);
+        let renter = untrack(renter_saver);
+        let owner_taxes = Taxpayer::annual_taxes(&owner);
+        vec![
+            (
+                if owner_taxes.itemized { "Owner Taxes (itemized)" } else { "Owner Taxes (standard)" },
+                Taxpayer::monthly_taxes(&owner),
+            ),
+            ("Renter Taxes", Taxpayer::monthly_taxes(&renter)),
+        ]
+    };
+
     create_effect(move |_| {
         y_axis_max.get();
         expand_methodology.get();
-        age.get();
-        networth.get();
-        retirement_age.get();
-        monthly_income.get();
-        monthly_expenses.get();
-        rent.get();
-        home_value.get();
-        mortgage.get();
-        mortgage_rate.get();
-        mortgage_term.get();
-        min_retirement_income.get();
-        max_retirement_income.get();
-        find_equivelent_rent.get();
-        savers_derived.get();
-        inflation_rates.get();
-        interest_rates.get();
+        simulation.track();
         plot_resource.refetch();
-        if pause_resume.get() {
-            pause();
-        } else {
-            resume();
-        }
     });
 
     view! {
@@ -769,12 +1814,26 @@ fn App() -> impl IntoView {
                 </div>
                 <div id="plot-container-action-button">
                     <button on:click=move |_| {
-                        set_pause_resume.set(!pause_resume.get());
                         set_find_equivelent_rent.set(false);
+                        set_seed.set(new_seed());
                     }>
-
-                        {move || { if pause_resume.get() { "Resume" } else { "Pause" } }}
+                        "Rerun Simulation"
                     </button>
+                    <Show when=move || simulating.get()>
+                        <span>"Simulating... "</span>
+                    </Show>
+                    <label for="seed">"Seed "</label>
+                    <input
+                        id="seed"
+                        type="text"
+                        prop:value=move || seed.get().to_string()
+                        on:change=move |ev| {
+                            match event_target_value(&ev).trim().parse() {
+                                Ok(new_seed) => set_seed.set(new_seed),
+                                Err(_) => set_seed.set(seed.get_untracked()),
+                            }
+                        }
+                    />
                     <button
                         id="methodology-button"
                         on:click=move |_| {
@@ -808,7 +1867,6 @@ fn App() -> impl IntoView {
 
                     </button>
                     <button on:click={move |_| {
-                        set_pause_resume.set(true);
                         set_find_equivelent_rent.set(true);
                     }}>
                         "Find Equivelent Rent"
@@ -820,39 +1878,463 @@ fn App() -> impl IntoView {
                     <DisplayOptions set_val=set_y_axis_max fn_meta=y_axis_opts/>
                 </div>
             </Show>
+            <div id="cost-breakdown-container">
+                <h3>Monthly Home Costs</h3>
+                {move || cost_breakdown()
+                    .into_iter()
+                    .map(|(name, cost)| view! {
+                        <p>
+                            {name} ": "
+                            {(cost.round() as i32).to_formatted_string(&Locale::en)}
+                        </p>
+                    })
+                    .collect_view()}
+                {move || prepay_summary()
+                    .into_iter()
+                    .map(|(name, value)| view! { <p>{name} ": " {value}</p> })
+                    .collect_view()}
+                {move || outcome_summary()
+                    .into_iter()
+                    .map(|(name, value)| view! { <p>{name} ": " {value}</p> })
+                    .collect_view()}
+                <Show when=move || rate_paths.get() == 1>
+                    <p>"Historical mortgage rates are synthetic: each year's inflation plus 3%"</p>
+                </Show>
+                {move || simulation.with(|simulation| {
+                    simulation.first_withdrawal_error.map(|error| view! {
+                        <p>
+                            {simulation.withdrawal_errors} " runs stopped early, " {error.to_string()}
+                        </p>
+                    })
+                })}
+                {move || tax_summary()
+                    .into_iter()
+                    .map(|(name, cost)| view! {
+                        <p>
+                            {name} ": "
+                            {(cost.round() as i32).to_formatted_string(&Locale::en)}
+                        </p>
+                    })
+                    .collect_view()}
+            </div>
             <Show when=move || expand_methodology.try_get().unwrap_or(true)>
                 <div id="methodology-container">
                     <h3>Methodology</h3>
+                    <h4>Simulation</h4>
+                    <p>
+                        "This calculator compares the savings of a home owner, a renter and a renter who
+                        buys later. The simulation runs 1000 rate paths, feeding each path to all three,
+                        and the chart shows the median savings by age with a shaded band from the 10th to
+                        the 90th percentile. The seed next to the rerun button reproduces a simulation.
+                        Interest and inflation compound monthly at the annual rate / 12. Inflation grows
+                        rent, HOA dues, home insurance, expenses and income. Only liquid savings earn
+                        interest, the home does not. The success rate is the share of runs whose savings
+                        last until the end."
+                    </p>
+                    <h4>Return Models</h4>
+                    <p>
+                        "Uniform draws stock returns evenly from -7.5% to 20% plus half of inflation.
+                        Normal and lognormal draw from the mean return and std dev, and Student-t has
+                        fatter tails the fewer its degrees of freedom. Block bootstrap strings together
+                        runs of consecutive historical years. Regime switching moves between a bull market
+                        at the mean return and a bear market that starts with the bear market chance and
+                        lasts the bear market years on average."
+                    </p>
+                    <h4>Correlated Assets</h4>
+                    <p>
+                        "Stock and bond returns, inflation, rent growth, home price growth and mortgage
+                        rates are drawn together each year from their means, std devs and correlations,
+                        using a Cholesky factor of the correlation matrix. Inflation, rents, home prices
+                        and mortgage rates carry part of last year's move into the next, and rents and
+                        home prices follow their own paths instead of inflation and the appreciation rate."
+                    </p>
+                    <h4>Historical Backtest</h4>
+                    <p>
+                        "Historical paths replay S&P 500 and 10 year treasury returns, 3 month treasury
+                        bills and CPI inflation from 1928 on, one run for every start year whose whole run
+                        fits in the history. Mortgage rates are synthetic, 3% above that year's inflation.
+                        The worst start year is the run that ends with the least in today's dollars."
+                    </p>
+                    <h4>Asset Allocation</h4>
+                    <p>
+                        "Savings earn the allocation's mix of stock, bond and cash returns. Age bands move
+                        from 80% stocks when young to 40% late in life, with the rest in bonds. The target
+                        date glide path holds 90% stocks until 25 years before retirement, reaches 50%
+                        stocks and 5% cash at retirement and settles at 30% stocks 7 years after. A custom
+                        glide path moves in a straight line between its breakpoints. Annual rebalancing
+                        resets the mix to the target, without it the mix drifts with the returns. The ROI
+                        and Beta rows under the chart are the median return and its spread over each
+                        stretch of the glide path."
+                    </p>
+                    <h4>Home and Mortgage</h4>
+                    <p>
+                        "The home value grows with inflation plus the real appreciation rate, and property
+                        tax and maintenance grow with it. The owner already owns the home, so only buying
+                        a home later pays closing costs, the down payment is already home equity. At the
+                        sale age the equity minus selling costs becomes liquid savings and rent starts
+                        from today's rent grown with inflation, a sale age of 0 keeps the home. The buy
+                        later saver rents until the buy later age, then buys a home of the chosen value
+                        grown with home prices at that year's mortgage rate, waiting a year at a time
+                        until savings cover the down payment and closing costs."
+                    </p>
+                    <h4>Refinancing and Adjustable Rates</h4>
+                    <p>
+                        "A refinance, at the chosen age or when the market rate drops far enough below
+                        yours, re-amortizes the remaining mortgage at the market rate over the refinance
+                        term and charges the refinance costs. An adjustable rate mortgage keeps its rate
+                        for the fixed years, then resets every year to the index rate plus the margin,
+                        limited by the periodic and lifetime caps (dotted lines on the right axis)."
+                    </p>
+                    <h4>Extra Principal and PMI</h4>
+                    <p>
+                        "Extra principal comes out of liquid savings, and the chart also shows the owner
+                        investing that money instead, with both payoff ages and the interest saved.
+                        Private mortgage insurance is charged until paydown or appreciation brings the
+                        loan under the loan to value cutoff."
+                    </p>
+                    <h4>Taxes</h4>
+                    <p>
+                        "With a filing status chosen, income pays the federal brackets, a flat state tax
+                        and payroll taxes, with brackets and deductions grown with inflation. The larger
+                        of the standard deduction or itemizing mortgage interest plus property and state
+                        taxes up to the $10,000 SALT cap is taken. Savings on hand at the start are fully
+                        paid in, and selling savings pays long term capital gains tax on its share of the
+                        gains. Selling the home pays the same tax on the gain over the purchase price and
+                        closing costs, less the primary residence exclusion."
+                    </p>
+                    <h4>Retirement Accounts</h4>
+                    <p>
+                        "Pre-tax contributions and the employer match come out of income before tax, roth
+                        contributions after, up to the annual limits. Spending beyond income draws the
+                        accounts down in the withdrawal order: pre-tax withdrawals are taxed as income,
+                        roth withdrawals are tax free and taxable withdrawals pay capital gains tax. From
+                        the RMD age a required slice of pre-tax savings is taxed and moved to taxable."
+                    </p>
+                    <h4>Withdrawal Strategies</h4>
+                    <p>
+                        "Retirement spending is set once a year, between the min and max monthly
+                        retirement income. Constant dollar takes the withdrawal rate of the first year's
+                        savings and grows it with inflation, constant percent takes the rate every year,
+                        the guardrails cut or raise spending 10% when the rate moves 20% from the start,
+                        VPW spends savings down by age 100 at a 4% real return, and RMD based divides
+                        savings by the IRS distribution period."
+                    </p>
+                    <h4>Benefits</h4>
+                    <p>
+                        "Social security starts at the claiming age and grows with inflation. A pension
+                        grows with inflation until it starts and afterwards only with its cost of living
+                        adjustment. Withdrawals only cover the gap between benefits and spending, and 85%
+                        of social security and all of a pension are taxed."
+                    </p>
+                    <h4>Partner</h4>
+                    <p>
+                        "A partner shares your savings and home with their own age, income, retirement age,
+                        benefits, wage base and contribution limits. The run goes on until the younger of
+                        you reaches 100. A pension stops when its member dies, and the survivor keeps the
+                        larger social security benefit."
+                    </p>
+                    <h4>Retirement Spending</h4>
+                    <p>
+                        "Living expenses carry into retirement changed by the retirement expense change.
+                        Healthcare starts at retirement and grows by inflation plus the healthcare
+                        inflation. The spending smile lowers real expenses through early retirement and
+                        raises them late in life. Withdrawals always cover expenses, healthcare and housing."
+                    </p>
+                    <h4>Wages</h4>
+                    <p>
+                        "Income grows with inflation plus the real wage growth profile: flat adds nothing,
+                        linear the same raise every year, and hump-shaped starts at the real wage growth at
+                        25 and shrinks it to nothing by the peak earnings age. Wage volatility adds a yearly
+                        shock that moves with the market return by the wage market correlation."
+                    </p>
+                    <h4>Life Events</h4>
                     <p>
-                        "This calculator compares the savings of a renter vs a home owner.
-                        A Monte Carlo simulation is used to calculate stock market returns
-                        and inflation rates. The simulation run 1000 times would produce 
-                        interest rates starting close to the following: 6.25% and falls to
-                        4.5% as you age. Additionally the std deviation of the interest rates
-                        also decreases as you age to assume less risk is introduced the less
-                        time you have to recover from a market crash. Both interest and inflation
-                        are compounded monthly using an annual interest rate / 12.0. Inflation is 
-                        impacts rent, home expenses (1% annually), monthly expenses, and monthly income.
-                        Interest is only applied to liquid assets (home value is not interest bearing).
-                        We assume you continue to live in the same home for the duration of the simulation.
-                        "
+                        "A windfall adds to savings, an income change raises or cuts income, a recurring
+                        expense adds to spending, and unemployment stops income, each for its span of ages
+                        in today's dollars. Events are marked on the chart where they start."
                     </p>
 
                 </div>
             </Show>
+            <Show when=move || rate_paths.get() == 2>
+                <div id="assets-container">
+                    <h3>Correlated Assets</h3>
+                    <table>
+                        <tr>
+                            <th></th>
+                            <th>"Mean"</th>
+                            <th>"Std Dev"</th>
+                            {ALL_ASSETS.iter().map(|asset| view! { <th>{asset.label()}</th> }).collect_view()}
+                        </tr>
+                        {ALL_ASSETS
+                            .iter()
+                            .enumerate()
+                            .map(|(row, asset)| view! {
+                                <tr>
+                                    <th>{asset.label()}</th>
+                                    <td>
+                                        <input
+                                            type="number"
+                                            step="0.005"
+                                            prop:value=move || asset_model.get().means[row]
+                                            on:change=move |ev| {
+                                                if let Ok(mean) = event_target_value(&ev).parse() {
+                                                    set_asset_model.update(|model| model.means[row] = mean);
+                                                }
+                                            }
+                                        />
+                                    </td>
+                                    <td>
+                                        <input
+                                            type="number"
+                                            step="0.005"
+                                            min="0"
+                                            prop:value=move || asset_model.get().std_devs[row]
+                                            on:change=move |ev| {
+                                                if let Ok(std_dev) = event_target_value(&ev).parse::<f32>() {
+                                                    set_asset_model.update(|model| model.std_devs[row] = std_dev.max(0.0));
+                                                }
+                                            }
+                                        />
+                                    </td>
+                                    {ALL_ASSETS
+                                        .iter()
+                                        .enumerate()
+                                        .map(|(col, other)| {
+                                            let (asset, other) = (*asset, *other);
+                                            if col > row {
+                                                view! {
+                                                    <td>
+                                                        <input
+                                                            type="number"
+                                                            step="0.05"
+                                                            min="-1"
+                                                            max="1"
+                                                            prop:value=move || asset_model.get().correlations[row][col]
+                                                            on:change=move |ev| {
+                                                                if let Ok(correlation) = event_target_value(&ev).parse() {
+                                                                    set_asset_model.update(|model| {
+                                                                        model.set_correlation(asset, other, correlation)
+                                                                    });
+                                                                }
+                                                            }
+                                                        />
+                                                    </td>
+                                                }
+                                            } else {
+                                                view! {
+                                                    <td>{move || asset_model.get().correlations[row][col].to_string()}</td>
+                                                }
+                                            }
+                                        })
+                                        .collect_view()}
+                                </tr>
+                            })
+                            .collect_view()}
+                    </table>
+                    <Show when=move || !asset_model.get().is_consistent()>
+                        <p>"These correlations can't all hold at once, so they are shrunk toward zero until they can."</p>
+                    </Show>
+                    <button on:click=move |_| set_asset_model.set(AssetModel::default())>
+                        "Reset Assets"
+                    </button>
+                </div>
+            </Show>
+            <Show when=move || allocation.get() == 2>
+                <div id="allocation-container">
+                    <h3>Glide Path</h3>
+                    <DisplayOptions set_val=set_glide_age fn_meta=glide_age_opts/>
+                    <DisplayOptions set_val=set_glide_stocks fn_meta=glide_stocks_opts/>
+                    <DisplayOptions set_val=set_glide_bonds fn_meta=glide_bonds_opts/>
+                    <button on:click=move |_| {
+                        set_glide_points.update(|points| {
+                            let point = new_glide_point();
+                            points.retain(|(age, _)| *age != point.0);
+                            points.push(point);
+                            points.sort_by_key(|(age, _)| *age);
+                        });
+                    }>
+                        "Add Breakpoint"
+                    </button>
+                    {move || glide_points
+                        .get()
+                        .into_iter()
+                        .enumerate()
+                        .map(|(i, (age, mix))| view! {
+                            <p>
+                                {format!(
+                                    "At {}: {:.0}% stocks, {:.0}% bonds, {:.0}% cash",
+                                    age,
+                                    mix.stocks * 100.0,
+                                    mix.bonds * 100.0,
+                                    mix.cash * 100.0
+                                )} " "
+                                <button on:click=move |_| {
+                                    set_glide_points.update(|points| {
+                                        points.remove(i);
+                                    });
+                                }>
+                                    "Remove"
+                                </button>
+                            </p>
+                        })
+                        .collect_view()}
+                </div>
+            </Show>
+            <div id="events-container">
+                <h3>Life Events</h3>
+                <DisplayChoices set_val=set_event_type fn_meta=event_type_opts/>
+                <DisplayOptions set_val=set_event_start_age fn_meta=event_start_age_opts/>
+                <Show when=move || event_type.get() != 0>
+                    <DisplayOptions set_val=set_event_end_age fn_meta=event_end_age_opts/>
+                </Show>
+                <Show when=move || event_type.get() == 0>
+                    <DisplayOptions set_val=set_event_amount fn_meta=event_amount_opts/>
+                </Show>
+                <Show when=move || event_type.get() == 1>
+                    <DisplayOptions set_val=set_event_change fn_meta=event_change_opts/>
+                </Show>
+                <Show when=move || event_type.get() == 2>
+                    <DisplayOptions set_val=set_event_monthly fn_meta=event_monthly_opts/>
+                </Show>
+                <button on:click=move |_| {
+                    set_life_events.update(|events| events.push(new_event()));
+                }>
+                    "Add Event"
+                </button>
+                {move || life_events
+                    .get()
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, event)| view! {
+                        <p>
+                            {event.label()} " "
+                            {if event.start_age() == event.end_age() {
+                                format!("at {}", event.start_age())
+                            } else {
+                                format!("from {} to {}", event.start_age(), event.end_age())
+                            }} " "
+                            <button on:click=move |_| {
+                                set_life_events.update(|events| {
+                                    events.remove(i);
+                                });
+                            }>
+                                "Remove"
+                            </button>
+                        </p>
+                    })
+                    .collect_view()}
+            </div>
             <div id="opts-container">
+                <DisplayChoices set_val=set_rate_paths fn_meta=rate_paths_opts/>
+                <Show when=move || rate_paths.get() == 0>
+                    <DisplayChoices set_val=set_return_model fn_meta=return_model_opts/>
+                    <Show when=move || [1, 2, 3, 5].contains(&return_model.get())>
+                        <DisplayOptions set_val=set_mean_return fn_meta=mean_return_opts/>
+                        <DisplayOptions set_val=set_return_std_dev fn_meta=return_std_dev_opts/>
+                    </Show>
+                    <Show when=move || return_model.get() == 3>
+                        <DisplayOptions set_val=set_degrees_of_freedom fn_meta=degrees_of_freedom_opts/>
+                    </Show>
+                    <Show when=move || return_model.get() == 4>
+                        <DisplayOptions set_val=set_block_years fn_meta=block_years_opts/>
+                    </Show>
+                    <Show when=move || return_model.get() == 5>
+                        <DisplayOptions set_val=set_bear_return fn_meta=bear_return_opts/>
+                        <DisplayOptions set_val=set_bear_chance fn_meta=bear_chance_opts/>
+                        <DisplayOptions set_val=set_bear_years fn_meta=bear_years_opts/>
+                    </Show>
+                </Show>
+                <DisplayChoices set_val=set_allocation fn_meta=allocation_opts/>
+                <DisplayChoices set_val=set_rebalancing fn_meta=rebalancing_opts/>
                 <DisplayOptions set_val=set_age fn_meta=age_opts/>
                 <DisplayOptions set_val=set_networth fn_meta=networth_opts/>
                 <DisplayOptions set_val=set_retirement_age fn_meta=retirement_age_opts/>
                 <DisplayOptions set_val=set_monthly_income fn_meta=monthly_income_opts/>
+                <DisplayChoices set_val=set_wage_profile fn_meta=wage_profile_opts/>
+                <Show when=move || wage_profile.get() != 0>
+                    <DisplayOptions set_val=set_wage_growth fn_meta=wage_growth_opts/>
+                </Show>
+                <Show when=move || wage_profile.get() == 2>
+                    <DisplayOptions set_val=set_peak_earnings_age fn_meta=peak_earnings_age_opts/>
+                </Show>
+                <DisplayOptions set_val=set_wage_volatility fn_meta=wage_volatility_opts/>
+                <Show when=move || wage_volatility.get().get_float_ref() != &0.0>
+                    <DisplayOptions set_val=set_wage_market_correlation fn_meta=wage_market_correlation_opts/>
+                </Show>
                 <DisplayOptions set_val=set_monthly_expenses fn_meta=monthly_expenses_opts/>
                 <DisplayOptions set_val=set_rent fn_meta=rent_opts/>
                 <DisplayOptions set_val=set_home_value fn_meta=home_value_opts/>
+                <DisplayOptions set_val=set_home_appreciation fn_meta=home_appreciation_opts/>
+                <DisplayOptions set_val=set_property_tax fn_meta=property_tax_opts/>
+                <DisplayOptions set_val=set_maintenance fn_meta=maintenance_opts/>
+                <DisplayOptions set_val=set_hoa fn_meta=hoa_opts/>
+                <DisplayOptions set_val=set_insurance fn_meta=insurance_opts/>
+                <DisplayOptions set_val=set_closing_costs fn_meta=closing_costs_opts/>
+                <DisplayOptions set_val=set_selling_costs fn_meta=selling_costs_opts/>
+                <DisplayOptions set_val=set_sale_age fn_meta=sale_age_opts/>
+                <DisplayOptions set_val=set_purchase_age fn_meta=purchase_age_opts/>
+                <DisplayOptions set_val=set_purchase_home_value fn_meta=purchase_home_value_opts/>
+                <DisplayOptions set_val=set_down_payment fn_meta=down_payment_opts/>
                 <DisplayOptions set_val=set_mortgage fn_meta=mortgage_opts/>
                 <Show when=move || mortgage.get().get_float_ref() != &0.0>
                     <DisplayOptions set_val=set_mortgage_rate fn_meta=mortgage_rate_opts/>
                     <DisplayOptions set_val=set_mortgage_term fn_meta=mortgage_term_opts/>
+                    <DisplayOptions set_val=set_refinance_age fn_meta=refinance_age_opts/>
+                    <DisplayOptions set_val=set_refinance_rate_drop fn_meta=refinance_rate_drop_opts/>
+                    <DisplayOptions set_val=set_refinance_term fn_meta=refinance_term_opts/>
+                    <DisplayOptions set_val=set_refinance_costs fn_meta=refinance_costs_opts/>
+                    <DisplayOptions set_val=set_arm_fixed_years fn_meta=arm_fixed_years_opts/>
+                    <Show when=move || arm_fixed_years.get().get_int() != 0>
+                        <DisplayOptions set_val=set_arm_margin fn_meta=arm_margin_opts/>
+                        <DisplayOptions set_val=set_arm_periodic_cap fn_meta=arm_periodic_cap_opts/>
+                        <DisplayOptions set_val=set_arm_lifetime_cap fn_meta=arm_lifetime_cap_opts/>
+                    </Show>
+                    <DisplayOptions set_val=set_extra_monthly_principal fn_meta=extra_monthly_principal_opts/>
+                    <DisplayOptions set_val=set_extra_annual_principal fn_meta=extra_annual_principal_opts/>
+                    <DisplayOptions set_val=set_pmi_rate fn_meta=pmi_rate_opts/>
+                    <DisplayOptions set_val=set_pmi_ltv_cutoff fn_meta=pmi_ltv_cutoff_opts/>
+                </Show>
+                <DisplayChoices set_val=set_filing_status fn_meta=filing_status_opts/>
+                <Show when=move || filing_status.get() != 0>
+                    <DisplayOptions set_val=set_state_tax fn_meta=state_tax_opts/>
+                    <DisplayOptions set_val=set_home_sale_exclusion fn_meta=home_sale_exclusion_opts/>
                 </Show>
+                <DisplayOptions set_val=set_pre_tax_savings fn_meta=pre_tax_savings_opts/>
+                <DisplayOptions set_val=set_roth_savings fn_meta=roth_savings_opts/>
+                <DisplayOptions set_val=set_pre_tax_contribution fn_meta=pre_tax_contribution_opts/>
+                <DisplayOptions set_val=set_employer_match fn_meta=employer_match_opts/>
+                <DisplayOptions set_val=set_roth_contribution fn_meta=roth_contribution_opts/>
+                <DisplayOptions set_val=set_rmd_age fn_meta=rmd_age_opts/>
+                <DisplayChoices set_val=set_withdrawal_order fn_meta=withdrawal_order_opts/>
+                <DisplayChoices set_val=set_withdrawal_strategy fn_meta=withdrawal_strategy_opts/>
+                <Show when=move || withdrawal_strategy.get() <= 2>
+                    <DisplayOptions set_val=set_withdrawal_rate fn_meta=withdrawal_rate_opts/>
+                </Show>
+                <DisplayOptions set_val=set_social_security_age fn_meta=social_security_age_opts/>
+                <DisplayOptions set_val=set_social_security fn_meta=social_security_opts/>
+                <DisplayOptions set_val=set_pension fn_meta=pension_opts/>
+                <Show when=move || pension.get().get_float_ref() != &0.0>
+                    <DisplayOptions set_val=set_pension_age fn_meta=pension_age_opts/>
+                    <DisplayChoices set_val=set_pension_cola fn_meta=pension_cola_opts/>
+                </Show>
+                <DisplayChoices set_val=set_has_partner fn_meta=has_partner_opts/>
+                <Show when=move || has_partner.get() == 1>
+                    <DisplayOptions set_val=set_partner_age fn_meta=partner_age_opts/>
+                    <DisplayOptions set_val=set_partner_retirement_age fn_meta=partner_retirement_age_opts/>
+                    <DisplayOptions set_val=set_partner_income fn_meta=partner_income_opts/>
+                    <DisplayOptions set_val=set_partner_social_security_age fn_meta=partner_social_security_age_opts/>
+                    <DisplayOptions set_val=set_partner_social_security fn_meta=partner_social_security_opts/>
+                    <DisplayOptions set_val=set_partner_pension fn_meta=partner_pension_opts/>
+                    <Show when=move || partner_pension.get().get_float_ref() != &0.0>
+                        <DisplayOptions set_val=set_partner_pension_age fn_meta=partner_pension_age_opts/>
+                        <DisplayChoices set_val=set_partner_pension_cola fn_meta=partner_pension_cola_opts/>
+                    </Show>
+                </Show>
+                <DisplayOptions set_val=set_retirement_expense_change fn_meta=retirement_expense_change_opts/>
+                <DisplayOptions set_val=set_healthcare fn_meta=healthcare_opts/>
+                <DisplayOptions set_val=set_healthcare_inflation fn_meta=healthcare_inflation_opts/>
+                <DisplayChoices set_val=set_spending_smile fn_meta=spending_smile_opts/>
                 <DisplayOptions
                     set_val=set_min_retirement_income
                     fn_meta=min_retirement_income_opts
@@ -885,13 +2367,15 @@ where
                 <select
                     id=name.clone()
                     on:change=move |ev| {
-                        match numtype {
-                            OptType::Int => {
-                                set_val.set(Opts::opt_from_u8_str(&event_target_value(&ev)))
-                            }
-                            OptType::Float => {
-                                set_val.set(Opts::opt_from_f32_str(&event_target_value(&ev)))
-                            }
+                        let value = event_target_value(&ev);
+                        let parsed = match numtype {
+                            OptType::Int => Opts::opt_from_u8_str(&value).map_err(|e| e.to_string()),
+                            OptType::Float => Opts::opt_from_f32_str(&value).map_err(|e| e.to_string()),
+                        };
+                        // a value that doesn't parse leaves the option as it was
+                        match parsed {
+                            Ok(opt) => set_val.set(opt),
+                            Err(e) => logging::error!("Error parsing {}: {}", value, e),
                         }
                     }
                 >
@@ -906,10 +2390,61 @@ where
     }
 }
 
+#[component]
+fn DisplayChoices<FnMeta>(set_val: WriteSignal<u8>, fn_meta: FnMeta) -> impl IntoView
+where
+    FnMeta: Fn() -> ChoiceMeta + 'static,
+{
+    let ChoiceMeta {
+        name,
+        info,
+        default_val,
+        choices,
+    } = fn_meta();
+    view! {
+        <div id="select-container">
+            <div id="select-container-options">
+                <label for=name.clone()>{name.clone()}</label>
+                <select
+                    id=name.clone()
+                    on:change=move |ev| {
+                        set_val.set(event_target_value(&ev).parse().unwrap_or(default_val))
+                    }
+                >
+
+                    {choices
+                        .iter()
+                        .enumerate()
+                        .map(|(i, choice)| {
+                            view! {
+                                <option selected=i == default_val as usize value=i>
+                                    {*choice}
+                                </option>
+                            }
+                        })
+                        .collect_view()}
+                </select>
+            </div>
+            <div id="select-container-footnotes">
+                <p>{info.clone()}</p>
+            </div>
+        </div>
+    }
+}
+
+// one decimal place, or two when a rate needs them like 2.75%
+fn percent(rate: f32) -> String {
+    let val = format!("{:.2}", rate * 100.0);
+    match val.strip_suffix('0') {
+        Some(val) => format!("{}%", val),
+        None => format!("{}%", val),
+    }
+}
+
 #[component]
 fn SelectOpts<FnDefaults>(options: FnDefaults) -> impl IntoView
 where
-    FnDefaults: Fn() -> (&'static [Opts; 100], Opts) + 'static,
+    FnDefaults: Fn() -> (&'static [Opts], Opts) + 'static,
 {
     move || {
         let (options, default_val) = options();
@@ -917,15 +2452,17 @@ where
         if let Some(last_val) = options.last() {
             match last_val {
                 Opts::Int(_) => {}
+                // rates and shares up to 100% are shown as percents
                 Opts::Float(x) => {
-                    if x < &1.0 {
+                    if x <= &1.0 {
                         format_percent = true;
                     }
                 }
             }
         }
-        (*options)
-            .into_iter()
+        options
+            .iter()
+            .copied()
             .map(|opt| match opt {
                 Opts::Int(opt) => {
                     if opt == default_val.get_int() {
@@ -944,13 +2481,7 @@ where
                             <option selected="selected" value=opt>
 
                                 {if format_percent {
-                                    let mut val = ((opt * 1000.0).trunc() as i32)
-                                        .to_formatted_string(&Locale::en) + "%";
-                                    val.insert(val.len() - 2, '.');
-                                    if val == ".0%" {
-                                        val = "0.0%".to_string();
-                                    }
-                                    val
+                                    percent(opt)
                                 } else {
                                     let mut val = (opt.trunc() as i32)
                                         .to_formatted_string(&Locale::en);
@@ -967,13 +2498,7 @@ where
                             <option value=opt>
 
                                 {if format_percent {
-                                    let mut val = ((opt * 1000.0).trunc() as i32)
-                                        .to_formatted_string(&Locale::en) + "%";
-                                    val.insert(val.len() - 2, '.');
-                                    if val == ".0%" {
-                                        val = "0.0%".to_string();
-                                    }
-                                    val
+                                    percent(opt)
                                 } else {
                                     let mut val = (opt.trunc() as i32)
                                         .to_formatted_string(&Locale::en);